@@ -1,9 +1,112 @@
+#![allow(clippy::needless_return)]
+
+extern crate constants;
 extern crate utilities;
-mod pi;
+use constants::pi;
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use utilities::arguments;
+use utilities::digits_format::DigitFormat;
+
+const PROGRESS_BAR_WIDTH: usize = 40;
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if arguments::has_flag(&args, "--stream") {
+        return stream_digits(arguments::flag_value(&args, "--count"));
+    }
+    if let Some(digits) = arguments::flag_value(&args, "--digits") {
+        return match DigitFormat::from_args(&args) {
+            Ok(format) => print_with_progress(digits, &args, format),
+            Err(error) => eprintln!("{}", error),
+        };
+    }
+    match arguments::flag_value(&args, "--hex") {
+        Some(offset) => print_hex_digits(offset, arguments::flag_value(&args, "--count")),
+        None => print_decimal_digits(),
+    }
+}
+
+fn print_decimal_digits() {
     let title = "Enter the number of Pi decimals:";
     let decimals_count = utilities::read_number_input::read_non_negative_number(title);
     let pi = pi::calculate(decimals_count);
     println!("π: {}", pi);
 }
+
+/// Usage: `find_pi_n_digits --digits <n> [--threads <t>] [--resume <file>]
+/// [--radix <2-36>] [--group <n>] [--line <n>] [--output <file>]`
+/// Uses every available core unless a thread count is given, and renders a
+/// progress bar on stderr while the computation runs. With `--resume` the
+/// progress is saved to the file, and picked up from it if it already exists.
+fn print_with_progress(number_of_digits: u64, args: &[String], format: DigitFormat) {
+    let (sender, receiver) = mpsc::channel();
+    let mut options = pi::Options::new(format.decimals_needed(number_of_digits));
+    options.threads = match arguments::flag_value(args, "--threads") {
+        Some(threads) => threads,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    options.progress = Some(sender);
+    options.checkpoint = arguments::flag_string(args, "--resume").map(PathBuf::from);
+    let worker = thread::spawn(move || pi::calculate_with(options));
+    let started = Instant::now();
+    for progress in receiver {
+        eprint!("\r{}", progress_bar(progress, started.elapsed()));
+    }
+    eprintln!();
+    match worker.join().expect("Pi calculation panicked") {
+        Ok(pi) => {
+            let formatted = format.format(&pi, number_of_digits);
+            if let Err(error) = format.write("π: ", &formatted) {
+                eprintln!("Could not write the digits: {}", error);
+            }
+        }
+        Err(error) => eprintln!("Checkpoint error: {}", error),
+    }
+}
+
+fn progress_bar(progress: pi::Progress, elapsed: Duration) -> String {
+    let ratio = progress.completed as f64 / progress.total as f64;
+    let filled = (ratio * PROGRESS_BAR_WIDTH as f64) as usize;
+    let eta = elapsed.as_secs_f64() * (1.0 - ratio) / ratio;
+    return format!(
+        "[{}{}] {:>3}% ETA {:.0}s",
+        "#".repeat(filled),
+        " ".repeat(PROGRESS_BAR_WIDTH - filled),
+        (ratio * 100.0) as u32,
+        eta
+    );
+}
+
+/// Usage: `find_pi_n_digits --hex <offset> [--count <n>]`
+fn print_hex_digits(offset: u64, count: Option<u64>) {
+    match count {
+        Some(count) => {
+            let digits = pi::hex_digits(offset, count);
+            println!("π (hex) [{}..{}]: {}", offset, offset + count, digits);
+        }
+        None => println!("π (hex) [{}]: {:X}", offset, pi::hex_digit_at(offset)),
+    }
+}
+
+/// Usage: `find_pi_n_digits --stream [--count <n>]`
+/// Writes the digits as they are produced, forever when no count is given.
+fn stream_digits(count: Option<u64>) {
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+    let digits = pi::digits().take(count.unwrap_or(u64::MAX) as usize);
+    for (index, digit) in digits.enumerate() {
+        let separator = if index == 1 { "." } else { "" };
+        if write!(output, "{}{}", separator, digit)
+            .and_then(|_| output.flush())
+            .is_err()
+        {
+            return;
+        }
+    }
+    println!();
+}
@@ -1,9 +1,35 @@
-mod e;
+#![allow(clippy::needless_return)]
+
+extern crate constants;
 extern crate utilities;
+use constants::e;
+use std::env;
+use utilities::arguments;
+use utilities::digits_format::DigitFormat;
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match arguments::flag_value(&args, "--digits") {
+        Some(number_of_digits) => match DigitFormat::from_args(&args) {
+            Ok(format) => print_formatted(number_of_digits, format),
+            Err(error) => eprintln!("{}", error),
+        },
+        None => print_decimal_digits(),
+    }
+}
+
+fn print_decimal_digits() {
     let title = "Enter the number of decimals of e";
     let number_of_decimals = utilities::read_number_input::read_non_negative_number(title);
-    let result = e::calculate(number_of_decimals);
+    let result = e::calculate(number_of_decimals as u64);
     println!("e = {}", result);
 }
+
+/// Usage: `find_e_n_digits --digits <n> [--radix <2-36>] [--group <n>] [--line <n>] [--output <file>]`
+fn print_formatted(number_of_digits: u64, format: DigitFormat) {
+    let result = e::calculate(format.decimals_needed(number_of_digits));
+    let formatted = format.format(&result, number_of_digits);
+    if let Err(error) = format.write("e = ", &formatted) {
+        eprintln!("Could not write the digits: {}", error);
+    }
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::{black_box, BenchmarkId, Criterion};
+use fibonacci::fibonacci;
+
+fn compare_small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fibonacci_small");
+    for n in [10_u32, 20, 30].iter() {
+        group.bench_with_input(BenchmarkId::new("recursive", n), n, |b, &n| {
+            b.iter(|| fibonacci::nth_recursive(black_box(n)))
+        });
+        group.bench_with_input(BenchmarkId::new("linear", n), n, |b, &n| {
+            b.iter(|| fibonacci::nth_linear(black_box(n as u64)))
+        });
+        group.bench_with_input(BenchmarkId::new("fast_doubling", n), n, |b, &n| {
+            b.iter(|| fibonacci::nth(black_box(n as u64)))
+        });
+    }
+    group.finish();
+}
+
+fn compare_large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fibonacci_large");
+    group.sample_size(10);
+    for n in [1_000_u64, 10_000, 100_000].iter() {
+        group.bench_with_input(BenchmarkId::new("linear", n), n, |b, &n| {
+            b.iter(|| fibonacci::nth_linear(black_box(n)))
+        });
+        group.bench_with_input(BenchmarkId::new("fast_doubling", n), n, |b, &n| {
+            b.iter(|| fibonacci::nth(black_box(n)))
+        });
+    }
+    group.finish();
+}
+
+fn modular(c: &mut Criterion) {
+    c.bench_function("nth_mod 10^18", |b| {
+        b.iter(|| fibonacci::nth_mod(black_box(1_000_000_000_000_000_000), 1_000_000_007))
+    });
+}
+
+criterion_group!(benches, compare_small, compare_large, modular);
+criterion_main!(benches);
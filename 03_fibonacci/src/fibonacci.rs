@@ -1,82 +1,427 @@
-use std::collections::HashMap;
+extern crate num_bigint;
+extern crate num_traits;
 
-pub fn sequence(n: u32) -> String {
-    let fib_sequence = fibonacci_sequence(n + 1);
-    return stringify_sequence(fib_sequence);
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::mem;
+
+/// `count` consecutive Fibonacci numbers, from F(start) on.
+pub fn sequence(start: u64, count: u64) -> Vec<BigUint> {
+    return Fibonacci::starting_at(start).take(count as usize).collect();
 }
 
-fn stringify_sequence(seq: [u128; 128]) -> String {
-    let sequence_strings: Vec<String> = seq
-        .iter()
-        .filter(|&&n| n != 0)
-        .map(|n| n.to_string())
-        .collect();
-    return sequence_strings.join(" ");
-}
-
-fn fibonacci_sequence(n: u32) -> [u128; 128] {
-    let mut buff = [0; 128];
-    let mut memo: HashMap<u32, u128> = HashMap::new();
-    for i in 0..n {
-        match memo.get(&i) {
-            Some(value) => buff[i as usize] = *value,
-            None => {
-                buff[i as usize] = memoized_fibonacci(i, &memo);
-                memo.insert(i, buff[i as usize]);
-            }
+/// Lazy iterator over F(0), F(1), F(2), ...
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+    current: BigUint,
+    next: BigUint,
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        return Fibonacci {
+            current: BigUint::zero(),
+            next: BigUint::one(),
+        };
+    }
+
+    /// Iterator over F(n), F(n + 1), ... that skips the first terms with fast doubling.
+    pub fn starting_at(n: u64) -> Fibonacci {
+        return Fibonacci {
+            current: nth(n),
+            next: nth(n + 1),
+        };
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Fibonacci {
+        return Fibonacci::new();
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let following = &self.current + &self.next;
+        let next = mem::replace(&mut self.next, following);
+        return Some(mem::replace(&mut self.current, next));
+    }
+}
+
+/// The n-th Fibonacci number, with F(0) = 0 and F(1) = 1.
+/// Uses fast doubling, so only O(log n) big integer multiplications are needed:
+/// F(2k) = F(k) * (2 * F(k + 1) - F(k))
+/// F(2k + 1) = F(k)² + F(k + 1)²
+pub fn nth(n: u64) -> BigUint {
+    let mut current = BigUint::zero();
+    let mut next = BigUint::one();
+    for bit in (0..64 - n.leading_zeros()).rev() {
+        let double = &current * (&next * 2_u32 - &current);
+        let double_plus_one = &current * &current + &next * &next;
+        if (n >> bit) & 1 == 0 {
+            current = double;
+            next = double_plus_one;
+        } else {
+            next = &double + &double_plus_one;
+            current = double_plus_one;
+        }
+    }
+    return current;
+}
+
+/// F(n) mod m, using fast doubling on residues.
+pub fn nth_mod(n: u64, m: u64) -> u64 {
+    assert!(m > 0, "The modulus needs to be bigger than 0");
+    let m = m as u128;
+    let mut current = 0_u128;
+    let mut next = 1 % m;
+    for bit in (0..64 - n.leading_zeros()).rev() {
+        let double = current * ((2 * next + m - current) % m) % m;
+        let double_plus_one = (current * current % m + next * next % m) % m;
+        if (n >> bit) & 1 == 0 {
+            current = double;
+            next = double_plus_one;
+        } else {
+            next = (double + double_plus_one) % m;
+            current = double_plus_one;
         }
     }
-    return buff;
+    return current as u64;
 }
 
-fn memoized_fibonacci(n: u32, memo: &HashMap<u32, u128>) -> u128 {
-    match memo.get(&n) {
-        Some(value) => return *value,
-        None => fibonacci(n),
+/// The n-th Fibonacci number adding up every previous term, O(n) additions.
+/// Kept as a baseline for the benchmarks.
+pub fn nth_linear(n: u64) -> BigUint {
+    let mut current = BigUint::zero();
+    let mut next = BigUint::one();
+    for _ in 0..n {
+        let sum = &current + &next;
+        current = next;
+        next = sum;
     }
+    return current;
 }
 
-fn fibonacci(n: u32) -> u128 {
+/// The n-th Fibonacci number with the naive exponential recursion.
+/// Kept as a baseline for the benchmarks.
+pub fn nth_recursive(n: u32) -> u128 {
     match n {
         0 => 0,
         1 => 1,
         2 => 1,
-        _ => fibonacci(n - 1) + fibonacci(n - 2),
+        _ => nth_recursive(n - 1) + nth_recursive(n - 2),
     }
 }
 
+/// Period of the Fibonacci sequence modulo m, π(m). Every period starts with 0, 1
+/// and is at most 6m long.
+/// https://en.wikipedia.org/wiki/Pisano_period
+pub fn pisano_period(m: u64) -> u64 {
+    assert!(m > 0, "The modulus needs to be bigger than 0");
+    if m == 1 {
+        return 1;
+    }
+    let m = m as u128;
+    let (mut current, mut next) = (0_u128, 1_u128);
+    let mut period = 0;
+    loop {
+        let following = (current + next) % m;
+        current = next;
+        next = following;
+        period += 1;
+        if current == 0 && next == 1 {
+            return period;
+        }
+    }
+}
+
+/// Decomposition of n into a sum of non consecutive Fibonacci numbers, largest first.
+/// https://en.wikipedia.org/wiki/Zeckendorf%27s_theorem
+pub fn zeckendorf(n: &BigUint) -> Vec<BigUint> {
+    // F(2), F(3), ... up to n, so F(1) = F(2) is never used twice.
+    let mut fibonacci: Vec<BigUint> = Fibonacci::new()
+        .skip(2)
+        .take_while(|value| value <= n)
+        .collect();
+    let mut remainder = n.clone();
+    let mut terms = Vec::new();
+    while let Some(value) = fibonacci.pop() {
+        if value <= remainder {
+            remainder -= &value;
+            terms.push(value);
+            // The next smaller Fibonacci number can't fit in what is left.
+            fibonacci.pop();
+        }
+    }
+    return terms;
+}
+
+/// Whether n is a Fibonacci number, that is 5n² + 4 or 5n² - 4 is a perfect square.
+pub fn is_fibonacci(n: &BigUint) -> bool {
+    let five_n_squared = n * n * 5_u32;
+    let plus = &five_n_squared + 4_u32;
+    if is_square(&plus) {
+        return true;
+    }
+    return five_n_squared >= BigUint::from(4_u32) && is_square(&(five_n_squared - 4_u32));
+}
+
+fn is_square(n: &BigUint) -> bool {
+    let root = n.sqrt();
+    return &root * &root == *n;
+}
+
+/// Whether n is a Fibonacci number that is also prime.
+pub fn is_fibonacci_prime(n: &BigUint) -> bool {
+    return is_fibonacci(n) && is_prime(n);
+}
+
+/// Pairs (k, F(k)) of the Fibonacci numbers that are prime: F(3) = 2, F(4) = 3,
+/// F(5) = 5, F(7) = 13, ... Only prime indices besides 4 can give a prime.
+pub fn fibonacci_primes() -> impl Iterator<Item = (u64, BigUint)> {
+    return (0_u64..)
+        .zip(Fibonacci::new())
+        .filter(|(k, _)| *k == 4 || is_prime(&BigUint::from(*k)))
+        .filter(|(_, value)| is_prime(value));
+}
+
+/// Miller-Rabin test with the first 20 primes as bases. Exact below 3.3 * 10^24,
+/// and a probable prime test with an error below 4^-20 for bigger numbers.
+pub fn is_prime(n: &BigUint) -> bool {
+    let two = BigUint::from(2_u32);
+    if *n < two {
+        return false;
+    }
+    for &prime in SMALL_PRIMES.iter() {
+        if *n == BigUint::from(prime) {
+            return true;
+        }
+        if (n % prime).is_zero() {
+            return false;
+        }
+    }
+    let n_minus_one = n - 1_u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+    'bases: for &base in SMALL_PRIMES.iter() {
+        let mut x = BigUint::from(base).modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    return true;
+}
+
+const SMALL_PRIMES: [u32; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use num_bigint::BigUint;
+    use std::convert::TryInto;
 
     #[test]
     fn test_fibonacci() {
-        assert_eq!(super::fibonacci(0), 0);
-        assert_eq!(super::fibonacci(1), 1);
-        assert_eq!(super::fibonacci(2), 1);
-        assert_eq!(super::fibonacci(3), 2);
-        assert_eq!(super::fibonacci(4), 3);
-        assert_eq!(super::fibonacci(8), 21);
-        assert_eq!(super::fibonacci(10), 55);
+        assert_eq!(super::nth(0), 0_u32.into());
+        assert_eq!(super::nth(1), 1_u32.into());
+        assert_eq!(super::nth(2), 1_u32.into());
+        assert_eq!(super::nth(3), 2_u32.into());
+        assert_eq!(super::nth(4), 3_u32.into());
+        assert_eq!(super::nth(8), 21_u32.into());
+        assert_eq!(super::nth(10), 55_u32.into());
+    }
+
+    #[test]
+    fn test_nth_beyond_u128() {
+        // F(186) is the largest Fibonacci number that fits in a u128
+        assert_eq!(
+            super::nth(186),
+            332825110087067562321196029789634457848_u128.into()
+        );
+        assert_eq!(
+            super::nth(187).to_string(),
+            "538522340430300790495419781092981030533"
+        );
+        assert_eq!(
+            super::nth(300).to_string(),
+            "222232244629420445529739893461909967206666939096499764990979600"
+        );
+    }
+
+    #[test]
+    fn test_nth_large() {
+        let f = super::nth(100_000).to_string();
+        assert_eq!(f.len(), 20_899);
+        assert!(f.starts_with("2597406934722172416615503402127591541488048538651769658472477070395253454351127368626555677283671674"));
+        assert!(f.ends_with("6875"));
     }
 
     #[test]
-    fn test_memoized_fibonacci_returns_data_from_hash() {
-        let mut memo: HashMap<u32, u128> = HashMap::new();
-        memo.insert(0, 1000);
-        assert_eq!(super::memoized_fibonacci(0, &memo), 1000);
+    fn test_nth_matches_linear_and_recursive() {
+        for n in 0..30 {
+            assert_eq!(super::nth(n), super::nth_recursive(n as u32).into());
+        }
+        for n in 0..1000 {
+            assert_eq!(super::nth(n), super::nth_linear(n));
+        }
+    }
+
+    #[test]
+    fn test_nth_mod() {
+        assert_eq!(super::nth_mod(0, 10), 0);
+        assert_eq!(super::nth_mod(10, 1000), 55);
+        assert_eq!(super::nth_mod(10, 7), 6);
+        assert_eq!(super::nth_mod(5, 1), 0);
+        assert_eq!(
+            super::nth_mod(300, 1_000_000_007),
+            (super::nth(300) % 1_000_000_007_u32).try_into().unwrap()
+        );
+        // Modulus close to u64::MAX doesn't overflow the intermediate products.
+        let m = u64::MAX - 58;
+        assert_eq!(
+            super::nth_mod(1000, m),
+            (super::nth(1000) % m).try_into().unwrap()
+        );
+        assert_eq!(
+            super::nth_mod(1_000_000_000_000_000_000, 1_000_000_007),
+            209_783_453
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The modulus needs to be bigger than 0")]
+    fn test_nth_mod_zero_modulus() {
+        super::nth_mod(10, 0);
+    }
+
+    #[test]
+    fn test_iterator() {
+        let first: Vec<BigUint> = super::Fibonacci::new().take(8).collect();
+        let expectation: Vec<BigUint> = vec![0_u32, 1, 1, 2, 3, 5, 8, 13]
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        assert_eq!(first, expectation);
+        let mut iterator = super::Fibonacci::new();
+        assert_eq!(iterator.nth(500), Some(super::nth(500)));
+    }
+
+    fn joined(sequence: Vec<BigUint>) -> String {
+        let strings: Vec<String> = sequence.iter().map(|n| n.to_string()).collect();
+        return strings.join(" ");
     }
 
     #[test]
     fn test_sequence() {
-        // 1 1 2 3 5 8 13 21 34 55 89 144 233 377 610 987 1597 2584 4181 6765 10946 17711 28657 46368 75025 121393 196418 317811 ...
-        assert_eq!(super::sequence(1), "1");
-        assert_eq!(super::sequence(2), "1 1");
-        assert_eq!(super::sequence(3), "1 1 2");
-        assert_eq!(super::sequence(4), "1 1 2 3");
-        assert_eq!(super::sequence(5), "1 1 2 3 5");
-        assert_eq!(super::sequence(6), "1 1 2 3 5 8");
-        assert_eq!(super::sequence(8), "1 1 2 3 5 8 13 21");
-        assert_eq!(super::sequence(25), "1 1 2 3 5 8 13 21 34 55 89 144 233 377 610 987 1597 2584 4181 6765 10946 17711 28657 46368 75025");
+        // 0 1 1 2 3 5 8 13 21 34 55 89 144 233 377 610 987 1597 2584 4181 6765 10946 17711 28657 46368 75025 121393 196418 317811 ...
+        assert_eq!(joined(super::sequence(0, 0)), "");
+        assert_eq!(joined(super::sequence(0, 1)), "0");
+        assert_eq!(joined(super::sequence(0, 3)), "0 1 1");
+        assert_eq!(joined(super::sequence(1, 1)), "1");
+        assert_eq!(joined(super::sequence(1, 2)), "1 1");
+        assert_eq!(joined(super::sequence(1, 3)), "1 1 2");
+        assert_eq!(joined(super::sequence(1, 4)), "1 1 2 3");
+        assert_eq!(joined(super::sequence(1, 5)), "1 1 2 3 5");
+        assert_eq!(joined(super::sequence(1, 6)), "1 1 2 3 5 8");
+        assert_eq!(joined(super::sequence(1, 8)), "1 1 2 3 5 8 13 21");
+        assert_eq!(joined(super::sequence(1, 25)), "1 1 2 3 5 8 13 21 34 55 89 144 233 377 610 987 1597 2584 4181 6765 10946 17711 28657 46368 75025");
+    }
+
+    #[test]
+    fn test_sequence_with_start() {
+        assert_eq!(joined(super::sequence(10, 4)), "55 89 144 233");
+        assert_eq!(super::sequence(1000, 2)[0], super::nth(1000));
+        assert_eq!(super::sequence(1000, 2)[1], super::nth(1001));
+    }
+
+    #[test]
+    fn test_sequence_beyond_128_elements() {
+        let sequence = super::sequence(1, 200);
+        assert_eq!(sequence.len(), 200);
+        assert_eq!(
+            sequence.last().unwrap().to_string(),
+            "280571172992510140037611932413038677189525"
+        );
+    }
+
+    #[test]
+    fn test_pisano_period() {
+        let periods: Vec<u64> = (1..=12).map(super::pisano_period).collect();
+        assert_eq!(periods, [1, 3, 8, 6, 20, 24, 16, 12, 24, 60, 10, 24]);
+        assert_eq!(super::pisano_period(1000), 1500);
+        assert_eq!(super::pisano_period(1_000_000), 1_500_000);
+    }
+
+    #[test]
+    fn test_zeckendorf() {
+        let decompose = |n: u32| -> Vec<u32> {
+            return super::zeckendorf(&BigUint::from(n))
+                .iter()
+                .map(|value| value.try_into().unwrap())
+                .collect();
+        };
+        assert_eq!(decompose(0), Vec::<u32>::new());
+        assert_eq!(decompose(1), [1]);
+        assert_eq!(decompose(4), [3, 1]);
+        assert_eq!(decompose(64), [55, 8, 1]);
+        assert_eq!(decompose(100), [89, 8, 3]);
+        assert_eq!(decompose(144), [144]);
+        for n in 0..2000_u32 {
+            let terms = super::zeckendorf(&BigUint::from(n));
+            assert_eq!(terms.iter().sum::<BigUint>(), BigUint::from(n));
+            for pair in terms.windows(2) {
+                // Two consecutive Fibonacci numbers would add up to the next one.
+                assert!(!super::is_fibonacci(&(&pair[0] + &pair[1])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_fibonacci() {
+        let fibonacci: Vec<u32> = (0..20_u32)
+            .filter(|n| super::is_fibonacci(&BigUint::from(*n)))
+            .collect();
+        assert_eq!(fibonacci, [0, 1, 2, 3, 5, 8, 13]);
+        assert!(super::is_fibonacci(&super::nth(1000)));
+        assert!(!super::is_fibonacci(&(super::nth(1000) + 1_u32)));
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u32> = (0..50_u32)
+            .filter(|n| super::is_prime(&BigUint::from(*n)))
+            .collect();
+        assert_eq!(
+            primes,
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+        );
+        // Carmichael numbers and a strong pseudoprime to the bases 2, 3, 5 and 7.
+        assert!(!super::is_prime(&BigUint::from(561_u32)));
+        assert!(!super::is_prime(&BigUint::from(3_215_031_751_u64)));
+        assert!(super::is_prime(&BigUint::from(1_000_000_007_u32)));
+        // 2^127 - 1
+        assert!(super::is_prime(&((BigUint::from(1_u32) << 127) - 1_u32)));
+    }
+
+    #[test]
+    fn test_fibonacci_primes() {
+        let indices: Vec<u64> = super::fibonacci_primes().take(16).map(|(k, _)| k).collect();
+        assert_eq!(
+            indices,
+            [3, 4, 5, 7, 11, 13, 17, 23, 29, 43, 47, 83, 131, 137, 359, 431]
+        );
+        assert!(super::is_fibonacci_prime(&BigUint::from(89_u32)));
+        assert!(!super::is_fibonacci_prime(&BigUint::from(34_u32)));
+        assert!(!super::is_fibonacci_prime(&BigUint::from(7_u32)));
     }
 }
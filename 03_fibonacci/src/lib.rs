@@ -0,0 +1,5 @@
+#![allow(clippy::needless_return)]
+
+pub mod fibonacci;
+pub mod output;
+pub mod recurrence;
@@ -1,9 +1,61 @@
-mod fibonacci;
+#![allow(clippy::needless_return)]
+
 extern crate utilities;
+use ::fibonacci::{fibonacci, output, recurrence};
+use output::Format;
+use recurrence::LinearRecurrence;
+use std::env;
+use utilities::arguments;
 
+/// Usage: `fibonacci [--sequence <name>] [--start <n>] [--count <n>]
+/// [--format plain|json|csv|lines]` or `fibonacci --nth <n>`, where the sequence is
+/// one of fibonacci, lucas, pell, tribonacci, tetranacci or <k>-bonacci with k up to 100.
 fn main() {
-    let title = "Enter the number of elements for Fibonacci sequence:";
-    let number_of_elements = utilities::read_number_input::read_non_negative_number(title);
-    let sequence = fibonacci::sequence(number_of_elements as u32);
-    println!("Fibonacci: [{}]", sequence);
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(n) = arguments::flag_value(&args, "--nth") {
+        return println!("F({}) = {}", n, fibonacci::nth(n));
+    }
+    let name = match arguments::flag_string(&args, "--sequence") {
+        Some(name) => name.to_lowercase(),
+        None => String::from("fibonacci"),
+    };
+    let sequence = match LinearRecurrence::find(&name) {
+        Some(sequence) => sequence,
+        None => {
+            eprintln!("Unknown sequence {}.", name);
+            return eprintln!(
+                "Choose one of {} or <k>-bonacci with k up to {}.",
+                recurrence::NAMES.join(", "),
+                recurrence::MAX_K
+            );
+        }
+    };
+    let format = match arguments::flag_string(&args, "--format") {
+        Some(name) => match Format::from_name(name) {
+            Some(format) => format,
+            None => return eprintln!("Unknown format {}, use plain, json, csv or lines.", name),
+        },
+        None => Format::Plain,
+    };
+    let start = arguments::flag_value(&args, "--start").unwrap_or(0);
+    let count: Option<u64> = arguments::flag_value(&args, "--count");
+    let (count, interactive) = match count {
+        Some(count) => (count, false),
+        None => {
+            let title = format!("Enter the number of elements for {} sequence:", name);
+            let count = utilities::read_number_input::read_non_negative_number(&title);
+            (count as u64, true)
+        }
+    };
+    let rendered = match name.as_str() {
+        "fibonacci" => format.render(start, &fibonacci::sequence(start, count)),
+        _ => {
+            let terms: Vec<_> = sequence.skip(start as usize).take(count as usize).collect();
+            format.render(start, &terms)
+        }
+    };
+    match (interactive, format) {
+        (true, Format::Plain) => println!("{}: [{}]", name, rendered),
+        _ => println!("{}", rendered),
+    }
 }
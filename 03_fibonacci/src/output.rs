@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+/// How a range of terms is written out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Space separated values.
+    Plain,
+    /// JSON array of numbers.
+    Json,
+    /// `index,value` rows below a header.
+    Csv,
+    /// One value per line.
+    Lines,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "plain" => Some(Format::Plain),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "lines" => Some(Format::Lines),
+            _ => None,
+        }
+    }
+
+    /// Writes the terms a(start), a(start + 1), ...
+    pub fn render<T: Display>(&self, start: u64, terms: &[T]) -> String {
+        let values = terms.iter().map(|term| term.to_string());
+        match self {
+            Format::Plain => values.collect::<Vec<String>>().join(" "),
+            Format::Json => format!("[{}]", values.collect::<Vec<String>>().join(", ")),
+            Format::Csv => {
+                let rows = values
+                    .enumerate()
+                    .map(|(offset, value)| format!("{},{}", start + offset as u64, value));
+                return std::iter::once(String::from("index,value"))
+                    .chain(rows)
+                    .collect::<Vec<String>>()
+                    .join("\n");
+            }
+            Format::Lines => values.collect::<Vec<String>>().join("\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+
+    const TERMS: [u32; 4] = [0, 1, 1, 2];
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Format::from_name("json"), Some(Format::Json));
+        assert_eq!(Format::from_name("xml"), None);
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(Format::Plain.render(0, &TERMS), "0 1 1 2");
+        assert_eq!(Format::Json.render(0, &TERMS), "[0, 1, 1, 2]");
+        assert_eq!(Format::Lines.render(0, &TERMS), "0\n1\n1\n2");
+        assert_eq!(
+            Format::Csv.render(5, &TERMS),
+            "index,value\n5,0\n6,1\n7,1\n8,2"
+        );
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let empty: [u32; 0] = [];
+        assert_eq!(Format::Plain.render(0, &empty), "");
+        assert_eq!(Format::Json.render(0, &empty), "[]");
+        assert_eq!(Format::Csv.render(0, &empty), "index,value");
+    }
+}
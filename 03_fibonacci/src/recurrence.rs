@@ -0,0 +1,187 @@
+extern crate num_bigint;
+
+use num_bigint::BigInt;
+use std::collections::VecDeque;
+
+/// Names of the predefined sequences, besides "<k>-bonacci" for k up to `MAX_K`.
+pub const NAMES: [&str; 5] = ["fibonacci", "lucas", "pell", "tribonacci", "tetranacci"];
+
+/// Largest k accepted in "<k>-bonacci" names, every term keeps the k previous ones.
+pub const MAX_K: usize = 100;
+
+/// Sequence where every term is a linear combination of the previous ones:
+/// a(n) = c(0) * a(n - 1) + c(1) * a(n - 2) + ... + c(k - 1) * a(n - k)
+/// Iterating yields a(0), a(1), a(2), ...
+#[derive(Debug, Clone)]
+pub struct LinearRecurrence {
+    coefficients: Vec<BigInt>,
+    /// The next k terms of the sequence, oldest first.
+    window: VecDeque<BigInt>,
+}
+
+impl LinearRecurrence {
+    /// `seeds` are a(0) to a(k - 1), `coefficients` are c(0) to c(k - 1).
+    pub fn new(seeds: Vec<BigInt>, coefficients: Vec<BigInt>) -> LinearRecurrence {
+        assert!(!seeds.is_empty(), "A recurrence needs at least one seed");
+        assert_eq!(
+            seeds.len(),
+            coefficients.len(),
+            "A recurrence needs as many seeds as coefficients"
+        );
+        return LinearRecurrence {
+            coefficients,
+            window: seeds.into_iter().collect(),
+        };
+    }
+
+    /// 0, 1, 1, 2, 3, 5, 8, ...
+    pub fn fibonacci() -> LinearRecurrence {
+        return LinearRecurrence::k_bonacci(2);
+    }
+
+    /// 2, 1, 3, 4, 7, 11, 18, ...
+    pub fn lucas() -> LinearRecurrence {
+        return LinearRecurrence::new(integers(&[2, 1]), integers(&[1, 1]));
+    }
+
+    /// 0, 1, 2, 5, 12, 29, 70, ...
+    pub fn pell() -> LinearRecurrence {
+        return LinearRecurrence::new(integers(&[0, 1]), integers(&[2, 1]));
+    }
+
+    /// 0, 0, 1, 1, 2, 4, 7, 13, ...
+    pub fn tribonacci() -> LinearRecurrence {
+        return LinearRecurrence::k_bonacci(3);
+    }
+
+    /// Every term is the sum of the k previous ones, seeded with k - 1 zeros and a one.
+    pub fn k_bonacci(k: usize) -> LinearRecurrence {
+        assert!(k > 0, "A k-bonacci sequence needs k > 0");
+        let mut seeds = vec![BigInt::from(0); k - 1];
+        seeds.push(BigInt::from(1));
+        return LinearRecurrence::new(seeds, vec![BigInt::from(1); k]);
+    }
+
+    /// Sequence picked by its name in `NAMES`, or "<k>-bonacci", e.g. "5-bonacci".
+    pub fn find(name: &str) -> Option<LinearRecurrence> {
+        match name {
+            "fibonacci" => Some(LinearRecurrence::fibonacci()),
+            "lucas" => Some(LinearRecurrence::lucas()),
+            "pell" => Some(LinearRecurrence::pell()),
+            "tribonacci" => Some(LinearRecurrence::tribonacci()),
+            "tetranacci" => Some(LinearRecurrence::k_bonacci(4)),
+            _ => {
+                let k = name.strip_suffix("-bonacci")?.parse().ok()?;
+                match k {
+                    1..=MAX_K => Some(LinearRecurrence::k_bonacci(k)),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for LinearRecurrence {
+    type Item = BigInt;
+
+    fn next(&mut self) -> Option<BigInt> {
+        let following: BigInt = self
+            .coefficients
+            .iter()
+            .zip(self.window.iter().rev())
+            .map(|(coefficient, term)| coefficient * term)
+            .sum();
+        self.window.push_back(following);
+        return self.window.pop_front();
+    }
+}
+
+fn integers(values: &[i64]) -> Vec<BigInt> {
+    return values.iter().map(|&value| BigInt::from(value)).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinearRecurrence;
+    use num_bigint::BigInt;
+
+    fn first(sequence: LinearRecurrence, n: usize) -> Vec<i64> {
+        return sequence
+            .take(n)
+            .map(|term| term.to_string().parse().unwrap())
+            .collect();
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        assert_eq!(
+            first(LinearRecurrence::fibonacci(), 10),
+            [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+        );
+    }
+
+    #[test]
+    fn test_lucas() {
+        assert_eq!(
+            first(LinearRecurrence::lucas(), 10),
+            [2, 1, 3, 4, 7, 11, 18, 29, 47, 76]
+        );
+    }
+
+    #[test]
+    fn test_pell() {
+        assert_eq!(
+            first(LinearRecurrence::pell(), 10),
+            [0, 1, 2, 5, 12, 29, 70, 169, 408, 985]
+        );
+    }
+
+    #[test]
+    fn test_k_bonacci() {
+        assert_eq!(
+            first(LinearRecurrence::tribonacci(), 10),
+            [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]
+        );
+        assert_eq!(
+            first(LinearRecurrence::k_bonacci(4), 10),
+            [0, 0, 0, 1, 1, 2, 4, 8, 15, 29]
+        );
+        assert_eq!(first(LinearRecurrence::k_bonacci(1), 4), [1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_custom_coefficients() {
+        // a(n) = 3 * a(n - 1) - 2 * a(n - 2) = 2^n - 1
+        let sequence = LinearRecurrence::new(
+            vec![BigInt::from(0), BigInt::from(1)],
+            vec![BigInt::from(3), BigInt::from(-2)],
+        );
+        assert_eq!(first(sequence, 6), [0, 1, 3, 7, 15, 31]);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            first(LinearRecurrence::find("lucas").unwrap(), 3),
+            [2, 1, 3]
+        );
+        assert_eq!(
+            first(LinearRecurrence::find("5-bonacci").unwrap(), 7),
+            [0, 0, 0, 0, 1, 1, 2]
+        );
+        for name in super::NAMES.iter() {
+            assert!(LinearRecurrence::find(name).is_some());
+        }
+        assert!(LinearRecurrence::find("0-bonacci").is_none());
+        assert!(LinearRecurrence::find("100-bonacci").is_some());
+        assert!(LinearRecurrence::find("101-bonacci").is_none());
+        assert!(LinearRecurrence::find("99999999999-bonacci").is_none());
+        assert!(LinearRecurrence::find("catalan").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "as many seeds as coefficients")]
+    fn test_mismatched_seeds() {
+        LinearRecurrence::new(vec![BigInt::from(1)], vec![]);
+    }
+}
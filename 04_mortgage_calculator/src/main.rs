@@ -1,10 +1,262 @@
-mod mortgage;
+#![allow(clippy::needless_return)]
+
 extern crate utilities;
+use mortgage::arm::{self, AdjustableRate, Caps};
+use mortgage::loan::{Compounding, Frequency, Loan};
+use mortgage::money::Money;
+use mortgage::offers::{self, Offer};
+use mortgage::scenario::{Prepayment, Scenario};
+use mortgage::schedule::Schedule;
+use mortgage::solver;
+use mortgage_calculator::mortgage;
+use std::env;
+use std::fs;
+use utilities::arguments;
+use utilities::read_number_input;
 
+/// Usage: `mortgage_calculator [--principal <p>] [--apr <percent>] [--years <y>]
+/// [--frequency monthly|bi-weekly|weekly] [--compounding payment|monthly|semi-annual|annual]
+/// [--extra <amount>] [--lump <period>:<amount>,...] [--schedule table|csv]
+/// [--output <file>]`. Missing values are asked for.
+/// Adjustable rate mortgages add `--arm <fixed years>/<years between resets>
+/// --index <percent>,... [--margin <percent>] [--caps <initial>/<periodic>/<lifetime>]`.
+/// `--solve principal|payments|rate --payment <amount>` finds the variable giving that payment.
+/// `--compare <name>:<apr>:<years>[:<points>[:<closing costs>]],...
+/// [--refinance <current payment>:<remaining payments>]` compares loan offers.
 fn main() {
-    let principal = utilities::read_number_input::read_float("principal: ");
-    let interest_rate = utilities::read_number_input::read_float("interest_rate: ");
-    let payments = utilities::read_number_input::read_number("payments: ");
-    let result = mortgage::calculate(principal, interest_rate, payments as i32);
+    let args: Vec<String> = env::args().skip(1).collect();
+    if arguments::has_flag(&args, "--compare") {
+        return match compare(&args) {
+            Ok(table) => println!("{}", table),
+            Err(error) => eprintln!("{}", error),
+        };
+    }
+    if let Some(variable) = arguments::flag_string(&args, "--solve") {
+        return match solve(&args, variable) {
+            Ok(solution) => println!("{}", solution),
+            Err(error) => eprintln!("{}", error),
+        };
+    }
+    let loan = match read_loan(&args, &[]) {
+        Ok(loan) => loan,
+        Err(error) => return eprintln!("{}", error),
+    };
+    let result = mortgage::calculate(
+        loan.principal,
+        loan.periodic_rate(),
+        loan.number_of_payments(),
+    );
     println!("Mortgage: {}", result);
+    if arguments::has_flag(&args, "--arm") {
+        return match read_arm(&args, loan) {
+            Ok(adjustable) => {
+                let (schedule, resets) = adjustable.schedule();
+                println!("{}", arm::resets_to_table(&resets));
+                print_schedule(&args, &schedule);
+            }
+            Err(error) => eprintln!("{}", error),
+        };
+    }
+    let scenario = match read_scenario(&args) {
+        Ok(scenario) => scenario,
+        Err(error) => return eprintln!("{}", error),
+    };
+    match scenario {
+        Some(scenario) => {
+            let rate = loan.periodic_rate();
+            let payments = loan.number_of_payments();
+            let comparison = scenario.compare(loan.principal, rate, payments);
+            println!("{}", comparison.to_report());
+            print_schedule(&args, &comparison.scenario);
+        }
+        None => print_schedule(&args, &loan.schedule()),
+    }
+}
+
+/// Loan from the arguments, asking for the missing values but the `unknown` ones.
+fn read_loan(args: &[String], unknown: &[&str]) -> Result<Loan, String> {
+    let read = |flag: &str, title: &str, variable: &str| -> f64 {
+        if unknown.contains(&variable) {
+            return 0.0;
+        }
+        return arguments::flag_value(args, flag)
+            .unwrap_or_else(|| read_number_input::read_float(title));
+    };
+    let principal = Money::from_f64(read("--principal", "principal: ", "principal"));
+    let annual_rate = read("--apr", "annual interest rate (%): ", "rate");
+    let years = read("--years", "years: ", "payments");
+    let frequency = match arguments::flag_string(args, "--frequency") {
+        Some(name) => Frequency::from_name(name).ok_or(format!(
+            "Unknown payment frequency {}, use monthly, bi-weekly or weekly",
+            name
+        ))?,
+        None => Frequency::Monthly,
+    };
+    let compounding = match arguments::flag_string(args, "--compounding") {
+        Some(name) => Compounding::from_name(name).ok_or(format!(
+            "Unknown compounding {}, use payment, monthly, semi-annual or annual",
+            name
+        ))?,
+        None => Compounding::EveryPayment,
+    };
+    if principal <= Money::ZERO && !unknown.contains(&"principal") {
+        return Err(String::from("The principal needs to be bigger than 0"));
+    }
+    if annual_rate < 0.0 {
+        return Err(String::from("The interest rate can't be negative"));
+    }
+    let loan = Loan {
+        principal,
+        annual_rate,
+        years,
+        frequency,
+        compounding,
+    };
+    if loan.number_of_payments() < 1 && !unknown.contains(&"payments") {
+        return Err(String::from("The loan needs at least one payment"));
+    }
+    return Ok(loan);
+}
+
+/// Principal, number of payments or annual rate of the loan paid off with `--payment`.
+fn solve(args: &[String], variable: &str) -> Result<String, String> {
+    if !["principal", "payments", "rate"].contains(&variable) {
+        return Err(format!(
+            "Unknown variable {}, solve for principal, payments or rate",
+            variable
+        ));
+    }
+    let loan = read_loan(args, &[variable])?;
+    let payment = arguments::flag_value(args, "--payment")
+        .unwrap_or_else(|| read_number_input::read_float("payment: "));
+    let payment = Money::from_f64(payment);
+    let rate = loan.periodic_rate();
+    let payments = loan.number_of_payments();
+    match variable {
+        "principal" => {
+            let principal = solver::principal(payment, rate, payments);
+            return Ok(format!("Principal: {}", principal));
+        }
+        "payments" => {
+            let payments = solver::number_of_payments(loan.principal, payment, rate)
+                .ok_or("The payment doesn't cover the interest")?;
+            let years = payments as f64 / loan.frequency.payments_per_year() as f64;
+            return Ok(format!("Payments: {} ({:.2} years)", payments, years));
+        }
+        _ => {
+            let rate = solver::rate(loan.principal, payment, payments)
+                .ok_or("The payments add up to less than the principal")?;
+            return Ok(format!("Annual rate: {:.4}%", loan.annual_rate_for(rate)));
+        }
+    }
+}
+
+/// Side by side table of the `--compare` offers for the loan's principal.
+fn compare(args: &[String]) -> Result<String, String> {
+    let list = arguments::flag_string(args, "--compare").map_or("", String::as_str);
+    let base = read_loan(args, &["rate", "payments"])?;
+    let offers = list
+        .split(',')
+        .map(|text| {
+            Offer::parse(text, &base).ok_or(format!(
+                "Invalid offer {}, use <name>:<apr>:<years>[:<points>[:<closing costs>]]",
+                text
+            ))
+        })
+        .collect::<Result<Vec<Offer>, String>>()?;
+    if offers
+        .iter()
+        .any(|offer| offer.loan.number_of_payments() < 1)
+    {
+        return Err(String::from("Every offer needs at least one payment"));
+    }
+    let current = match arguments::flag_string(args, "--refinance") {
+        Some(text) => Some(parse_current_loan(text).ok_or(format!(
+            "Invalid current loan {}, use <payment>:<remaining payments>",
+            text
+        ))?),
+        None => None,
+    };
+    return Ok(offers::to_table(&offers, current));
+}
+
+/// Parses "<payment>:<remaining payments>".
+fn parse_current_loan(text: &str) -> Option<(Money, i32)> {
+    let mut parts = text.splitn(2, ':');
+    let payment: f64 = parts.next()?.trim().parse().ok()?;
+    let remaining = parts.next()?.trim().parse().ok()?;
+    return Some((Money::from_f64(payment), remaining));
+}
+
+/// Adjustable rate mortgage starting with the loan's rate. Caps default to 2/2/5.
+fn read_arm(args: &[String], loan: Loan) -> Result<AdjustableRate, String> {
+    let notation = arguments::flag_string(args, "--arm").map_or("", String::as_str);
+    let margin = arguments::flag_value(args, "--margin").unwrap_or(0.0);
+    let index = match arguments::flag_string(args, "--index") {
+        Some(list) => list
+            .split(',')
+            .map(|value| value.trim().parse().ok())
+            .collect::<Option<Vec<f64>>>()
+            .ok_or(format!(
+                "Invalid index {}, use <percent>,<percent>,...",
+                list
+            ))?,
+        None => {
+            return Err(String::from(
+                "An adjustable rate needs --index <percent>,...",
+            ))
+        }
+    };
+    let caps = arguments::flag_string(args, "--caps").map_or("2/2/5", String::as_str);
+    let caps = Caps::parse(caps).ok_or(format!(
+        "Invalid caps {}, use <initial>/<periodic>/<lifetime>",
+        caps
+    ))?;
+    return AdjustableRate::new(loan, notation, margin, index, caps).ok_or(format!(
+        "Invalid adjustable rate {}, use <fixed years>/<years between resets>",
+        notation
+    ));
+}
+
+/// Extra payments from `--extra` and `--lump`, if any.
+fn read_scenario(args: &[String]) -> Result<Option<Scenario>, String> {
+    let extra_per_period = Money::from_f64(arguments::flag_value(args, "--extra").unwrap_or(0.0));
+    let prepayments = match arguments::flag_string(args, "--lump") {
+        Some(list) => list
+            .split(',')
+            .map(|text| {
+                Prepayment::parse(text).ok_or(format!(
+                    "Invalid prepayment {}, use <period>:<amount>",
+                    text
+                ))
+            })
+            .collect::<Result<Vec<Prepayment>, String>>()?,
+        None => vec![],
+    };
+    if extra_per_period < Money::ZERO {
+        return Err(String::from("The extra payment can't be negative"));
+    }
+    if extra_per_period == Money::ZERO && prepayments.is_empty() {
+        return Ok(None);
+    }
+    return Ok(Some(Scenario {
+        extra_per_period,
+        prepayments,
+    }));
+}
+
+fn print_schedule(args: &[String], schedule: &Schedule) {
+    let rendered = match arguments::flag_string(args, "--schedule").map(String::as_str) {
+        Some("csv") => schedule.to_csv(),
+        Some(_) => schedule.to_table(),
+        None => return,
+    };
+    match arguments::flag_string(args, "--output") {
+        Some(path) => {
+            if let Err(error) = fs::write(path, format!("{}\n", rendered)) {
+                eprintln!("Could not write the schedule: {}", error);
+            }
+        }
+        None => println!("{}", rendered),
+    }
 }
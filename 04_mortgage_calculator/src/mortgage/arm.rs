@@ -0,0 +1,249 @@
+use super::loan::Loan;
+use super::money::Money;
+use super::schedule::{Period, Schedule};
+
+/// Limits, in percentage points, on how much the rate of an adjustable rate mortgage moves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caps {
+    /// Maximum change at the first reset.
+    pub initial: f64,
+    /// Maximum change at every later reset.
+    pub periodic: f64,
+    /// Maximum increase over the initial rate during the whole loan.
+    pub lifetime: f64,
+}
+
+impl Caps {
+    /// Parses the usual "<initial>/<periodic>/<lifetime>" notation, e.g. "2/1/5".
+    pub fn parse(text: &str) -> Option<Caps> {
+        let caps: Vec<f64> = text
+            .split('/')
+            .map(|cap| cap.trim().parse().ok())
+            .collect::<Option<Vec<f64>>>()?;
+        match caps.as_slice() {
+            [initial, periodic, lifetime] => Some(Caps {
+                initial: *initial,
+                periodic: *periodic,
+                lifetime: *lifetime,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Adjustable rate mortgage: `loan.annual_rate` is kept for `fixed_periods` payments,
+/// then every `adjustment_interval` payments the rate resets to index plus margin,
+/// limited by the caps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustableRate {
+    pub loan: Loan,
+    pub fixed_periods: i32,
+    pub adjustment_interval: i32,
+    /// Annual rate in percent added to the index.
+    pub margin: f64,
+    /// Index in percent at each reset. The last value is used for every later reset.
+    pub index: Vec<f64>,
+    pub caps: Caps,
+}
+
+/// Rate and payment in effect from `period` on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reset {
+    pub period: i32,
+    pub annual_rate: f64,
+    pub payment: Money,
+}
+
+impl AdjustableRate {
+    /// ARM named with the usual "<fixed years>/<years between resets>" notation, e.g. "5/1".
+    pub fn new(
+        loan: Loan,
+        notation: &str,
+        margin: f64,
+        index: Vec<f64>,
+        caps: Caps,
+    ) -> Option<AdjustableRate> {
+        let mut parts = notation.splitn(2, '/');
+        let fixed_years: f64 = parts.next()?.trim().parse().ok()?;
+        let interval_years: f64 = parts.next()?.trim().parse().ok()?;
+        let payments_per_year = loan.frequency.payments_per_year() as f64;
+        let adjustment_interval = (interval_years * payments_per_year).round() as i32;
+        if fixed_years < 0.0 || adjustment_interval < 1 {
+            return None;
+        }
+        return Some(AdjustableRate {
+            fixed_periods: (fixed_years * payments_per_year).round() as i32,
+            adjustment_interval,
+            loan,
+            margin,
+            index,
+            caps,
+        });
+    }
+
+    /// Annual rate after reset number `reset`, counting from 0, when `previous` was in effect.
+    pub fn reset_rate(&self, reset: usize, previous: f64) -> f64 {
+        let index = match self.index.get(reset) {
+            Some(index) => *index,
+            None => *self.index.last().unwrap_or(&0.0),
+        };
+        let cap = match reset {
+            0 => self.caps.initial,
+            _ => self.caps.periodic,
+        };
+        let ceiling = self.loan.annual_rate + self.caps.lifetime;
+        let rate = (index + self.margin)
+            .max(previous - cap)
+            .min(previous + cap);
+        return rate.min(ceiling).max(0.0);
+    }
+
+    /// Payments of the loan and the rate resets, recomputing the payment to amortize
+    /// the remaining balance over the remaining term at every reset.
+    pub fn schedule(&self) -> (Schedule, Vec<Reset>) {
+        let number_of_payments = self.loan.number_of_payments();
+        let mut annual_rate = self.loan.annual_rate;
+        let mut interest_rate = self.periodic_rate(annual_rate);
+        let mut balance = self.loan.principal;
+        let mut payment = super::payment(balance, interest_rate, number_of_payments);
+        let mut resets = vec![Reset {
+            period: 1,
+            annual_rate,
+            payment,
+        }];
+        let mut periods = Vec::new();
+        for number in 1..=number_of_payments {
+            let since_fixed = number - self.fixed_periods - 1;
+            if since_fixed >= 0 && since_fixed % self.adjustment_interval == 0 {
+                let reset = (since_fixed / self.adjustment_interval) as usize;
+                annual_rate = self.reset_rate(reset, annual_rate);
+                interest_rate = self.periodic_rate(annual_rate);
+                let remaining = number_of_payments - number + 1;
+                payment = super::payment(balance, interest_rate, remaining);
+                resets.push(Reset {
+                    period: number,
+                    annual_rate,
+                    payment,
+                });
+            }
+            let period = Period::next(number, balance, interest_rate, |due| match number {
+                n if n == number_of_payments => due,
+                _ => payment.min(due),
+            });
+            balance = period.balance;
+            periods.push(period);
+        }
+        return (Schedule { periods }, resets);
+    }
+
+    fn periodic_rate(&self, annual_rate: f64) -> f64 {
+        let loan = Loan {
+            annual_rate,
+            ..self.loan.clone()
+        };
+        return loan.periodic_rate();
+    }
+}
+
+/// Lists the rate and payment at the start and after every reset.
+pub fn resets_to_table(resets: &[Reset]) -> String {
+    let mut lines = vec![format!("{:>6} {:>8} {:>12}", "Period", "Rate", "Payment")];
+    for reset in resets {
+        lines.push(format!(
+            "{:>6} {:>7.3}% {:>12}",
+            reset.period, reset.annual_rate, reset.payment
+        ));
+    }
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::loan::Loan;
+    use super::super::money::Money;
+    use super::{AdjustableRate, Caps};
+
+    fn arm(index: Vec<f64>) -> AdjustableRate {
+        let loan = Loan::new(Money::from_cents(20_000_000), 4.0, 30.0);
+        let caps = Caps::parse("2/2/5").unwrap();
+        return AdjustableRate::new(loan, "5/1", 2.75, index, caps).unwrap();
+    }
+
+    #[test]
+    fn test_new() {
+        let arm = arm(vec![3.0]);
+        assert_eq!(arm.fixed_periods, 60);
+        assert_eq!(arm.adjustment_interval, 12);
+        assert_eq!(Caps::parse("2/5"), None);
+        assert!(AdjustableRate::new(
+            Loan::new(Money::from_cents(100), 1.0, 1.0),
+            "5",
+            1.0,
+            vec![],
+            arm.caps.clone()
+        )
+        .is_none());
+        assert!(AdjustableRate::new(
+            Loan::new(Money::from_cents(100), 1.0, 1.0),
+            "-1/1",
+            1.0,
+            vec![],
+            arm.caps
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_reset_rate_caps() {
+        let arm = arm(vec![4.0, 6.0, 9.0]);
+        // 4 + 2.75 = 6.75 limited to 4 + 2 at the first reset.
+        assert_eq!(arm.reset_rate(0, 4.0), 6.0);
+        // 6 + 2.75 = 8.75 limited to 6 + 2.
+        assert_eq!(arm.reset_rate(1, 6.0), 8.0);
+        // 9 + 2.75 = 11.75 limited by the lifetime cap of 4 + 5.
+        assert_eq!(arm.reset_rate(2, 8.0), 9.0);
+        // The last index value is kept for later resets.
+        assert_eq!(arm.reset_rate(5, 9.0), 9.0);
+        // Falling rates are capped the same way.
+        assert_eq!(arm.reset_rate(0, 7.0), 6.75);
+        assert_eq!(arm.reset_rate(1, 10.0), 8.75);
+    }
+
+    #[test]
+    fn test_fixed_period_matches_fixed_rate_loan() {
+        let arm = arm(vec![1.25]);
+        let (schedule, resets) = arm.schedule();
+        let fixed = arm.loan.schedule();
+        assert_eq!(schedule.periods[..60], fixed.periods[..60]);
+        assert_eq!(resets.len(), 26);
+        assert_eq!(resets[1].period, 61);
+        assert_eq!(resets[1].annual_rate, 4.0);
+        assert_eq!(resets[1].payment, resets[0].payment);
+    }
+
+    #[test]
+    fn test_schedule_with_rising_rates() {
+        let arm = arm(vec![4.0, 6.0, 9.0]);
+        let (schedule, resets) = arm.schedule();
+        assert_eq!(schedule.periods.len(), 360);
+        assert_eq!(resets[0].payment.to_string(), "954.83");
+        assert_eq!(resets[1].annual_rate, 6.0);
+        assert_eq!(resets[1].payment.to_string(), "1165.51");
+        assert_eq!(resets[3].annual_rate, 9.0);
+        assert!(schedule.periods[61].payment > schedule.periods[59].payment);
+        let last = schedule.periods.last().unwrap();
+        assert_eq!(last.balance, Money::ZERO);
+        assert!(last.payment > Money::ZERO);
+    }
+
+    #[test]
+    fn test_resets_to_table() {
+        let arm = arm(vec![4.0]);
+        let (_, resets) = arm.schedule();
+        let table = super::resets_to_table(&resets[..2]);
+        assert_eq!(
+            table,
+            "Period     Rate      Payment\n     1   4.000%       954.83\n    61   6.000%      1165.51"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use super::money::Money;
+use super::schedule::Schedule;
+
+/// How often payments are made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Monthly,
+    BiWeekly,
+    Weekly,
+}
+
+impl Frequency {
+    pub fn from_name(name: &str) -> Option<Frequency> {
+        match name {
+            "monthly" => Some(Frequency::Monthly),
+            "bi-weekly" | "biweekly" => Some(Frequency::BiWeekly),
+            "weekly" => Some(Frequency::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn payments_per_year(&self) -> u32 {
+        match self {
+            Frequency::Monthly => 12,
+            Frequency::BiWeekly => 26,
+            Frequency::Weekly => 52,
+        }
+    }
+}
+
+/// How often interest is compounded, which may differ from the payment frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compounding {
+    /// Once every payment, the usual convention in the US.
+    EveryPayment,
+    Monthly,
+    /// Twice a year, required for fixed rate mortgages in Canada.
+    SemiAnnual,
+    Annual,
+}
+
+impl Compounding {
+    pub fn from_name(name: &str) -> Option<Compounding> {
+        match name {
+            "payment" => Some(Compounding::EveryPayment),
+            "monthly" => Some(Compounding::Monthly),
+            "semi-annual" | "canadian" => Some(Compounding::SemiAnnual),
+            "annual" => Some(Compounding::Annual),
+            _ => None,
+        }
+    }
+
+    fn periods_per_year(&self, frequency: Frequency) -> u32 {
+        match self {
+            Compounding::EveryPayment => frequency.payments_per_year(),
+            Compounding::Monthly => 12,
+            Compounding::SemiAnnual => 2,
+            Compounding::Annual => 1,
+        }
+    }
+}
+
+/// A fixed rate loan as quoted to borrowers: an annual rate in percent and a term in years.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loan {
+    pub principal: Money,
+    /// Nominal annual rate in percent, e.g. 5.0 for 5%.
+    pub annual_rate: f64,
+    pub years: f64,
+    pub frequency: Frequency,
+    pub compounding: Compounding,
+}
+
+impl Loan {
+    /// Monthly payments with interest compounded every payment.
+    pub fn new(principal: Money, annual_rate: f64, years: f64) -> Loan {
+        return Loan {
+            principal,
+            annual_rate,
+            years,
+            frequency: Frequency::Monthly,
+            compounding: Compounding::EveryPayment,
+        };
+    }
+
+    /// Interest rate of each payment period, equivalent to the annual rate compounded
+    /// with the loan's convention: (1 + r / c)^(c / p) - 1.
+    pub fn periodic_rate(&self) -> f64 {
+        let compounding = self.compounding.periods_per_year(self.frequency) as f64;
+        let payments = self.frequency.payments_per_year() as f64;
+        let rate_per_compounding = self.annual_rate / 100.0 / compounding;
+        return (1.0 + rate_per_compounding).powf(compounding / payments) - 1.0;
+    }
+
+    /// Nominal annual rate in percent whose periodic rate, with this loan's conventions,
+    /// is `periodic_rate`. The inverse of `periodic_rate`.
+    pub fn annual_rate_for(&self, periodic_rate: f64) -> f64 {
+        let compounding = self.compounding.periods_per_year(self.frequency) as f64;
+        let payments = self.frequency.payments_per_year() as f64;
+        return ((1.0 + periodic_rate).powf(payments / compounding) - 1.0) * compounding * 100.0;
+    }
+
+    pub fn number_of_payments(&self) -> i32 {
+        return (self.years * self.frequency.payments_per_year() as f64).round() as i32;
+    }
+
+    /// Payment of each period, rounded to the cent.
+    pub fn payment(&self) -> Money {
+        return super::payment(
+            self.principal,
+            self.periodic_rate(),
+            self.number_of_payments(),
+        );
+    }
+
+    pub fn schedule(&self) -> Schedule {
+        return Schedule::new(
+            self.principal,
+            self.periodic_rate(),
+            self.number_of_payments(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::money::Money;
+    use super::{Compounding, Frequency, Loan};
+
+    fn loan(dollars: i64, annual_rate: f64, years: f64) -> Loan {
+        return Loan::new(Money::from_cents(dollars * 100), annual_rate, years);
+    }
+
+    #[test]
+    fn test_monthly_payment() {
+        let loan = loan(100_000, 5.0, 25.0);
+        assert_eq!(loan.number_of_payments(), 300);
+        assert!((loan.periodic_rate() - 0.05 / 12.0).abs() < 1e-15);
+        assert_eq!(loan.payment().to_string(), "584.59");
+    }
+
+    #[test]
+    fn test_canadian_compounding() {
+        let mut loan = loan(100_000, 5.0, 25.0);
+        loan.compounding = Compounding::SemiAnnual;
+        assert!((loan.periodic_rate() - 0.004_123_915).abs() < 1e-9);
+        assert_eq!(loan.payment().to_string(), "581.60");
+        assert!((loan.annual_rate_for(loan.periodic_rate()) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bi_weekly_payments() {
+        let mut loan = loan(100_000, 5.0, 25.0);
+        loan.frequency = Frequency::BiWeekly;
+        assert_eq!(loan.number_of_payments(), 650);
+        assert_eq!(loan.payment().to_string(), "269.66");
+        loan.compounding = Compounding::Monthly;
+        assert_eq!(loan.payment().to_string(), "269.51");
+    }
+
+    #[test]
+    fn test_zero_interest() {
+        let loan = loan(120_000, 0.0, 10.0);
+        assert_eq!(loan.periodic_rate(), 0.0);
+        assert_eq!(loan.payment().to_string(), "1000.00");
+        let schedule = loan.schedule();
+        assert_eq!(schedule.total_interest(), Money::ZERO);
+        assert_eq!(schedule.periods.last().unwrap().balance, Money::ZERO);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Frequency::from_name("weekly"), Some(Frequency::Weekly));
+        assert_eq!(Frequency::from_name("daily"), None);
+        assert_eq!(
+            Compounding::from_name("canadian"),
+            Some(Compounding::SemiAnnual)
+        );
+        assert_eq!(Compounding::from_name("continuous"), None);
+    }
+}
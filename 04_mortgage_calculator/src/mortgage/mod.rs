@@ -1,15 +1,40 @@
+pub mod arm;
+pub mod loan;
+pub mod money;
+#[allow(clippy::module_inception)]
 mod mortgage;
+pub mod offers;
+pub mod scenario;
+pub mod schedule;
+pub mod solver;
 
-pub fn calculate(principal: f64, interest_rate: f64, number_of_payments: i32) -> String {
-    let result = mortgage::mortgage(principal, interest_rate, number_of_payments);
-    return format!("{:.2}", result);
+use money::Money;
+
+/// Formatted payment of each period.
+pub fn calculate(principal: Money, interest_rate: f64, number_of_payments: i32) -> String {
+    return payment(principal, interest_rate, number_of_payments).to_string();
+}
+
+/// Payment of each period, rounded to the nearest cent.
+pub fn payment(principal: Money, interest_rate: f64, number_of_payments: i32) -> Money {
+    assert!(number_of_payments > 0, "A loan needs at least one payment");
+    let result = mortgage::mortgage(principal.to_f64(), interest_rate, number_of_payments);
+    return Money::from_f64(result);
 }
 
 #[cfg(test)]
 mod tests {
+    use super::money::Money;
+
     #[test]
     fn test_calculate_mortgage() {
-        let monthly_payment = super::calculate(100_000.0, 0.005, 180);
+        let monthly_payment = super::calculate(Money::from_cents(10_000_000), 0.005, 180);
         assert_eq!(monthly_payment, "843.86");
     }
+
+    #[test]
+    #[should_panic(expected = "A loan needs at least one payment")]
+    fn test_no_payments() {
+        super::payment(Money::from_cents(10_000_000), 0.005, 0);
+    }
 }
@@ -0,0 +1,126 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// Amount of money as an exact number of cents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money {
+    cents: i64,
+}
+
+impl Money {
+    pub const ZERO: Money = Money { cents: 0 };
+
+    pub const fn from_cents(cents: i64) -> Money {
+        return Money { cents };
+    }
+
+    /// Rounds the amount to the nearest cent, halves away from zero.
+    pub fn from_f64(amount: f64) -> Money {
+        return Money {
+            cents: (amount * 100.0).round() as i64,
+        };
+    }
+
+    pub fn cents(&self) -> i64 {
+        return self.cents;
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        return self.cents as f64 / 100.0;
+    }
+
+    /// Interest accrued on this amount at `rate` per period, rounded to the nearest cent.
+    pub fn interest(&self, rate: f64) -> Money {
+        return Money {
+            cents: (self.cents as f64 * rate).round() as i64,
+        };
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let cents = self.cents.unsigned_abs();
+        return f.pad(&format!("{}{}.{:02}", sign, cents / 100, cents % 100));
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        return Money::from_cents(self.cents + other.cents);
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        return Money::from_cents(self.cents - other.cents);
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.cents += other.cents;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.cents -= other.cents;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        return Money::from_cents(iter.map(|money| money.cents).sum());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Money;
+
+    #[test]
+    fn test_from_f64_rounds_to_cents() {
+        assert_eq!(Money::from_f64(843.856).cents(), 84_386);
+        assert_eq!(Money::from_f64(0.125).cents(), 13);
+        assert_eq!(Money::from_f64(-0.125).cents(), -13);
+        assert_eq!(Money::from_f64(100_000.0).cents(), 10_000_000);
+    }
+
+    #[test]
+    fn test_interest() {
+        assert_eq!(
+            Money::from_cents(10_000_000).interest(0.005).cents(),
+            50_000
+        );
+        // 99656.14 * 0.005 = 498.2807
+        assert_eq!(Money::from_cents(9_965_614).interest(0.005).cents(), 49_828);
+        assert_eq!(Money::from_cents(100).interest(0.0).cents(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Money::from_cents(84_386).to_string(), "843.86");
+        assert_eq!(Money::from_cents(5).to_string(), "0.05");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::ZERO.to_string(), "0.00");
+        assert_eq!(format!("{:>8}", Money::from_cents(100)), "    1.00");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut money = Money::from_cents(100) + Money::from_cents(250);
+        assert_eq!(money, Money::from_cents(350));
+        money -= Money::from_cents(50);
+        assert_eq!(money - Money::from_cents(300), Money::ZERO);
+        let total: Money = vec![Money::from_cents(1), Money::from_cents(2)]
+            .into_iter()
+            .sum();
+        assert_eq!(total, Money::from_cents(3));
+    }
+}
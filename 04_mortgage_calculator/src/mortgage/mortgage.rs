@@ -1,4 +1,7 @@
 pub fn mortgage(principal: f64, interest_rate: f64, number_of_payments: i32) -> f64 {
+    if interest_rate == 0.0 {
+        return principal / number_of_payments as f64;
+    }
     let one_plus_rate = 1.0 + interest_rate;
     let one_plus_rate_pow_n = one_plus_rate.powi(number_of_payments);
     return principal * interest_rate * one_plus_rate_pow_n / (one_plus_rate_pow_n - 1.0);
@@ -12,4 +15,9 @@ mod tests {
         let rounded = (monthly_payment * 100.0).round() / 100.0;
         assert_eq!(rounded, 843.86);
     }
+
+    #[test]
+    fn test_zero_interest() {
+        assert_eq!(super::mortgage(120_000.0, 0.0, 240), 500.0);
+    }
 }
@@ -0,0 +1,189 @@
+use super::loan::Loan;
+use super::money::Money;
+use super::solver;
+
+/// A loan offer with its upfront fees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offer {
+    pub name: String,
+    pub loan: Loan,
+    /// Discount points, in percent of the principal.
+    pub points: f64,
+    pub closing_costs: Money,
+}
+
+impl Offer {
+    /// Parses "<name>:<annual rate>:<years>[:<points>[:<closing costs>]]", e.g.
+    /// "bank:6.25:30:1:3500", for a loan like `base` with that rate and term.
+    /// Missing or empty points and closing costs are 0.
+    pub fn parse(text: &str, base: &Loan) -> Option<Offer> {
+        let fields: Vec<&str> = text.split(':').map(str::trim).collect();
+        if fields.len() < 3 || fields.len() > 5 || fields[0].is_empty() {
+            return None;
+        }
+        let number = |index: usize| -> Option<f64> {
+            return match fields.get(index) {
+                Some(field) if !field.is_empty() => field.parse().ok(),
+                _ => Some(0.0),
+            };
+        };
+        return Some(Offer {
+            name: fields[0].to_string(),
+            loan: Loan {
+                annual_rate: number(1)?,
+                years: number(2)?,
+                ..base.clone()
+            },
+            points: number(3)?,
+            closing_costs: Money::from_f64(number(4)?),
+        });
+    }
+
+    /// Points plus closing costs, paid upfront.
+    pub fn fees(&self) -> Money {
+        return self.loan.principal.interest(self.points / 100.0) + self.closing_costs;
+    }
+
+    /// Every payment plus the fees.
+    pub fn total_cost(&self) -> Money {
+        return self.loan.schedule().total_cost() + self.fees();
+    }
+
+    /// Annual rate in percent of a loan of the principal minus the fees with the same
+    /// payments, so offers with different fees can be compared by a single rate.
+    pub fn apr(&self) -> Option<f64> {
+        let financed = self.loan.principal - self.fees();
+        let rate = solver::rate(
+            financed,
+            self.loan.payment(),
+            self.loan.number_of_payments(),
+        )?;
+        return Some(self.loan.annual_rate_for(rate));
+    }
+
+    /// First payment at which refinancing a loan paying `current_payment` for
+    /// `remaining_payments` more periods with this offer has saved its fees.
+    /// None when the payment doesn't go down or the savings never cover the fees.
+    pub fn break_even(&self, current_payment: Money, remaining_payments: i32) -> Option<i32> {
+        let saving = current_payment - self.loan.payment();
+        if saving <= Money::ZERO {
+            return None;
+        }
+        let mut saved = Money::ZERO;
+        for payment in 1..=remaining_payments.min(self.loan.number_of_payments()) {
+            saved += saving;
+            if saved >= self.fees() {
+                return Some(payment);
+            }
+        }
+        return None;
+    }
+}
+
+/// Side by side table of the offers, with their break-even payment when refinancing a
+/// loan paying `current` = (payment, remaining payments).
+pub fn to_table(offers: &[Offer], current: Option<(Money, i32)>) -> String {
+    let row = |title: &str, cell: &dyn Fn(&Offer) -> String| -> String {
+        let cells: Vec<String> = offers
+            .iter()
+            .map(|offer| format!("{:>14}", cell(offer)))
+            .collect();
+        return format!("{:<14}{}", title, cells.join(""));
+    };
+    let mut lines = vec![
+        row("", &|offer| offer.name.clone()),
+        row("Rate", &|offer| format!("{:.3}%", offer.loan.annual_rate)),
+        row("Years", &|offer| offer.loan.years.to_string()),
+        row("Points", &|offer| format!("{:.3}", offer.points)),
+        row("Fees", &|offer| offer.fees().to_string()),
+        row("Payment", &|offer| offer.loan.payment().to_string()),
+        row("Total cost", &|offer| offer.total_cost().to_string()),
+        row("APR", &|offer| match offer.apr() {
+            Some(apr) => format!("{:.3}%", apr),
+            None => String::from("-"),
+        }),
+    ];
+    if let Some((payment, remaining)) = current {
+        lines.push(row(
+            "Break-even",
+            &|offer| match offer.break_even(payment, remaining) {
+                Some(month) => month.to_string(),
+                None => String::from("never"),
+            },
+        ));
+    }
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::loan::Loan;
+    use super::super::money::Money;
+    use super::Offer;
+
+    fn base() -> Loan {
+        return Loan::new(Money::from_cents(30_000_000), 0.0, 0.0);
+    }
+
+    #[test]
+    fn test_parse() {
+        let offer = Offer::parse("bank:6.25:30:1:3500", &base()).unwrap();
+        assert_eq!(offer.name, "bank");
+        assert_eq!(offer.loan.annual_rate, 6.25);
+        assert_eq!(offer.loan.years, 30.0);
+        assert_eq!(offer.loan.principal, base().principal);
+        assert_eq!(offer.points, 1.0);
+        assert_eq!(offer.closing_costs, Money::from_cents(350_000));
+        let offer = Offer::parse("credit union:6.5:15", &base()).unwrap();
+        assert_eq!(offer.points, 0.0);
+        assert_eq!(offer.closing_costs, Money::ZERO);
+        let offer = Offer::parse("broker:6:30::2000", &base()).unwrap();
+        assert_eq!(offer.points, 0.0);
+        assert_eq!(offer.closing_costs, Money::from_cents(200_000));
+        assert_eq!(Offer::parse("bank:6.25", &base()), None);
+        assert_eq!(Offer::parse("bank:x:30", &base()), None);
+        assert_eq!(Offer::parse(":6:30", &base()), None);
+    }
+
+    #[test]
+    fn test_fees_and_total_cost() {
+        let offer = Offer::parse("bank:6:30:1:3500", &base()).unwrap();
+        assert_eq!(offer.fees().to_string(), "6500.00");
+        assert_eq!(offer.loan.payment().to_string(), "1798.65");
+        let payments = offer.loan.schedule().total_cost();
+        assert_eq!(offer.total_cost(), payments + offer.fees());
+    }
+
+    #[test]
+    fn test_apr() {
+        let no_fees = Offer::parse("bank:6:30", &base()).unwrap();
+        assert!((no_fees.apr().unwrap() - 6.0).abs() < 1e-3);
+        let with_fees = Offer::parse("bank:6:30:1:3500", &base()).unwrap();
+        let apr = with_fees.apr().unwrap();
+        assert!(apr > 6.2 && apr < 6.25, "APR {}", apr);
+    }
+
+    #[test]
+    fn test_break_even() {
+        let offer = Offer::parse("refinance:5:30:0:3000", &base()).unwrap();
+        // 1798.65 - 1610.46 = 188.19 saved every month, 3000 / 188.19 = 15.9
+        assert_eq!(offer.loan.payment().to_string(), "1610.46");
+        assert_eq!(offer.break_even(Money::from_cents(179_865), 300), Some(16));
+        assert_eq!(offer.break_even(Money::from_cents(179_865), 10), None);
+        assert_eq!(offer.break_even(Money::from_cents(150_000), 300), None);
+    }
+
+    #[test]
+    fn test_to_table() {
+        let offers = vec![
+            Offer::parse("A:6:30", &base()).unwrap(),
+            Offer::parse("B:5:30:0:3000", &base()).unwrap(),
+        ];
+        let table = super::to_table(&offers, Some((Money::from_cents(179_865), 300)));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "                           A             B");
+        assert_eq!(lines[5], "Payment              1798.65       1610.46");
+        assert_eq!(lines[8], "Break-even             never            16");
+    }
+}
@@ -0,0 +1,212 @@
+use super::money::Money;
+use super::schedule::Schedule;
+
+/// One-off payment on top of the regular one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prepayment {
+    pub period: i32,
+    pub amount: Money,
+}
+
+impl Prepayment {
+    /// Parses "<period>:<amount>", e.g. "12:5000". The period starts at 1 and the amount
+    /// can't be negative.
+    pub fn parse(text: &str) -> Option<Prepayment> {
+        let mut parts = text.splitn(2, ':');
+        let period = parts.next()?.trim().parse().ok()?;
+        let amount = Money::from_f64(parts.next()?.trim().parse().ok()?);
+        if period < 1 || amount < Money::ZERO {
+            return None;
+        }
+        return Some(Prepayment { period, amount });
+    }
+}
+
+/// Extra payments made on a loan besides the scheduled ones.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Scenario {
+    /// Paid every period on top of the regular payment.
+    pub extra_per_period: Money,
+    pub prepayments: Vec<Prepayment>,
+}
+
+impl Scenario {
+    /// Extra amount paid in the given period.
+    pub fn extra(&self, period: i32) -> Money {
+        let lump_sums: Money = self
+            .prepayments
+            .iter()
+            .filter(|prepayment| prepayment.period == period)
+            .map(|prepayment| prepayment.amount)
+            .sum();
+        return self.extra_per_period + lump_sums;
+    }
+
+    pub fn schedule(
+        &self,
+        principal: Money,
+        interest_rate: f64,
+        number_of_payments: i32,
+    ) -> Schedule {
+        return Schedule::with_extra_payments(
+            principal,
+            interest_rate,
+            number_of_payments,
+            |period| self.extra(period),
+        );
+    }
+
+    /// The loan paid following this scenario versus only the scheduled payments.
+    pub fn compare(
+        &self,
+        principal: Money,
+        interest_rate: f64,
+        number_of_payments: i32,
+    ) -> Comparison {
+        return Comparison {
+            baseline: Schedule::new(principal, interest_rate, number_of_payments),
+            scenario: self.schedule(principal, interest_rate, number_of_payments),
+        };
+    }
+}
+
+pub struct Comparison {
+    pub baseline: Schedule,
+    pub scenario: Schedule,
+}
+
+impl Comparison {
+    pub fn periods_saved(&self) -> usize {
+        return self.baseline.periods.len() - self.scenario.periods.len();
+    }
+
+    pub fn interest_saved(&self) -> Money {
+        return self.baseline.total_interest() - self.scenario.total_interest();
+    }
+
+    pub fn to_report(&self) -> String {
+        let row = |title: &str, baseline: Money, scenario: Money| -> String {
+            return format!(
+                "{:<16} {:>14} {:>14} {:>14}",
+                title,
+                baseline,
+                scenario,
+                baseline - scenario
+            );
+        };
+        return [
+            format!(
+                "{:<16} {:>14} {:>14} {:>14}",
+                "", "Baseline", "Extra payments", "Saved"
+            ),
+            format!(
+                "{:<16} {:>14} {:>14} {:>14}",
+                "Payments",
+                self.baseline.periods.len(),
+                self.scenario.periods.len(),
+                self.periods_saved()
+            ),
+            row(
+                "Total interest",
+                self.baseline.total_interest(),
+                self.scenario.total_interest(),
+            ),
+            row(
+                "Total cost",
+                self.baseline.total_cost(),
+                self.scenario.total_cost(),
+            ),
+        ]
+        .join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::money::Money;
+    use super::{Prepayment, Scenario};
+
+    const PRINCIPAL: Money = Money::from_cents(10_000_000);
+
+    #[test]
+    fn test_no_extra_payments_matches_baseline() {
+        let comparison = Scenario::default().compare(PRINCIPAL, 0.005, 180);
+        assert_eq!(comparison.periods_saved(), 0);
+        assert_eq!(comparison.interest_saved(), Money::ZERO);
+    }
+
+    #[test]
+    fn test_recurring_extra_payment() {
+        let scenario = Scenario {
+            extra_per_period: Money::from_cents(15_614),
+            prepayments: vec![],
+        };
+        let comparison = scenario.compare(PRINCIPAL, 0.005, 180);
+        // Paying 1000 instead of 843.86 every month.
+        assert_eq!(comparison.scenario.periods.len(), 139);
+        assert_eq!(comparison.periods_saved(), 41);
+        assert_eq!(comparison.interest_saved().to_string(), "12918.07");
+        let last = comparison.scenario.periods.last().unwrap();
+        assert_eq!(last.balance, Money::ZERO);
+        assert!(last.payment < Money::from_cents(100_000));
+    }
+
+    #[test]
+    fn test_lump_sum() {
+        let scenario = Scenario {
+            extra_per_period: Money::ZERO,
+            prepayments: vec![Prepayment {
+                period: 1,
+                amount: Money::from_cents(99_900),
+            }],
+        };
+        let schedule = scenario.schedule(Money::from_cents(100_000), 0.01, 2);
+        assert_eq!(schedule.periods.len(), 1);
+        assert_eq!(schedule.periods[0].payment.to_string(), "1010.00");
+        assert_eq!(schedule.periods[0].balance, Money::ZERO);
+    }
+
+    #[test]
+    fn test_extra() {
+        let scenario = Scenario {
+            extra_per_period: Money::from_cents(10_000),
+            prepayments: vec![
+                Prepayment::parse("12:5000").unwrap(),
+                Prepayment::parse("12: 1000").unwrap(),
+            ],
+        };
+        assert_eq!(scenario.extra(1).to_string(), "100.00");
+        assert_eq!(scenario.extra(12).to_string(), "6100.00");
+        assert_eq!(Prepayment::parse("12"), None);
+        assert_eq!(Prepayment::parse("a:100"), None);
+        assert_eq!(Prepayment::parse("12:-5000"), None);
+        assert_eq!(Prepayment::parse("0:5000"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Extra payments can't be negative")]
+    fn test_negative_extra_per_period() {
+        let scenario = Scenario {
+            extra_per_period: Money::from_cents(-100),
+            prepayments: vec![],
+        };
+        scenario.schedule(PRINCIPAL, 0.005, 180);
+    }
+
+    #[test]
+    fn test_report() {
+        let scenario = Scenario {
+            extra_per_period: Money::from_cents(15_614),
+            prepayments: vec![],
+        };
+        let report = scenario.compare(PRINCIPAL, 0.005, 180).to_report();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "Payments                    180            139             41"
+        );
+        assert!(lines[2].starts_with("Total interest"));
+        assert!(lines[2].ends_with("12918.07"));
+    }
+}
@@ -0,0 +1,206 @@
+use super::money::Money;
+
+/// One payment of an amortization schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Period {
+    pub number: i32,
+    pub payment: Money,
+    pub interest: Money,
+    pub principal: Money,
+    /// Balance left after the payment.
+    pub balance: Money,
+}
+
+/// Every payment of a fixed rate loan, from the first one to the one paying it off.
+/// Payments and interest are rounded to the cent, and the last payment is adjusted
+/// so the balance closes at exactly 0.00.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub periods: Vec<Period>,
+}
+
+impl Schedule {
+    pub fn new(principal: Money, interest_rate: f64, number_of_payments: i32) -> Schedule {
+        return Schedule::with_extra_payments(principal, interest_rate, number_of_payments, |_| {
+            Money::ZERO
+        });
+    }
+
+    /// Schedule where `extra(period)` is paid on top of the regular payment, which
+    /// pays the loan off before `number_of_payments` when it's positive. Negative extra
+    /// payments would grow the balance, so they panic.
+    pub fn with_extra_payments<F: Fn(i32) -> Money>(
+        principal: Money,
+        interest_rate: f64,
+        number_of_payments: i32,
+        extra: F,
+    ) -> Schedule {
+        let payment = super::payment(principal, interest_rate, number_of_payments);
+        let mut balance = principal;
+        let mut periods = Vec::new();
+        for number in 1..=number_of_payments {
+            let extra = extra(number);
+            assert!(extra >= Money::ZERO, "Extra payments can't be negative");
+            let period = Period::next(number, balance, interest_rate, |due| {
+                match number {
+                    // The last payment clears whatever the rounding of the previous ones left.
+                    n if n == number_of_payments => due,
+                    _ => (payment + extra).min(due),
+                }
+            });
+            balance = period.balance;
+            periods.push(period);
+            if balance == Money::ZERO {
+                break;
+            }
+        }
+        return Schedule { periods };
+    }
+
+    pub fn total_interest(&self) -> Money {
+        return self.periods.iter().map(|period| period.interest).sum();
+    }
+
+    /// Principal plus interest paid over the whole loan.
+    pub fn total_cost(&self) -> Money {
+        return self.periods.iter().map(|period| period.payment).sum();
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut lines = vec![format!(
+            "{:>6} {:>12} {:>12} {:>12} {:>14}",
+            "Period", "Payment", "Interest", "Principal", "Balance"
+        )];
+        for period in &self.periods {
+            lines.push(format!(
+                "{:>6} {:>12} {:>12} {:>12} {:>14}",
+                period.number, period.payment, period.interest, period.principal, period.balance
+            ));
+        }
+        lines.push(format!(
+            "{:>6} {:>12} {:>12} {:>12}",
+            "Total",
+            self.total_cost(),
+            self.total_interest(),
+            self.total_cost() - self.total_interest()
+        ));
+        return lines.join("\n");
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![String::from("period,payment,interest,principal,balance")];
+        for period in &self.periods {
+            lines.push(format!(
+                "{},{},{},{},{}",
+                period.number, period.payment, period.interest, period.principal, period.balance
+            ));
+        }
+        return lines.join("\n");
+    }
+}
+
+impl Period {
+    /// Period `number` starting with `balance`, where `payment(due)` decides how much
+    /// is paid out of the balance plus interest due.
+    pub fn next<F: FnOnce(Money) -> Money>(
+        number: i32,
+        balance: Money,
+        interest_rate: f64,
+        payment: F,
+    ) -> Period {
+        let interest = balance.interest(interest_rate);
+        let payment = payment(balance + interest);
+        let principal = payment - interest;
+        return Period {
+            number,
+            payment,
+            interest,
+            principal,
+            balance: balance - principal,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::money::Money;
+
+    #[test]
+    fn test_schedule() {
+        let schedule = super::Schedule::new(Money::from_cents(10_000_000), 0.005, 180);
+        assert_eq!(schedule.periods.len(), 180);
+        let first = &schedule.periods[0];
+        assert_eq!(first.payment.to_string(), "843.86");
+        assert_eq!(first.interest.to_string(), "500.00");
+        assert_eq!(first.principal.to_string(), "343.86");
+        assert_eq!(first.balance.to_string(), "99656.14");
+        let last = schedule.periods.last().unwrap();
+        assert_eq!(last.number, 180);
+        assert_eq!(last.payment.to_string(), "842.86");
+        assert_eq!(last.balance, Money::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "Extra payments can't be negative")]
+    fn test_negative_extra_payment() {
+        super::Schedule::with_extra_payments(Money::from_cents(10_000_000), 0.005, 180, |_| {
+            Money::from_cents(-100)
+        });
+    }
+
+    #[test]
+    fn test_schedule_closes_at_zero() {
+        for &(principal, rate, payments) in [
+            (10_000_000, 0.005, 180),
+            (30_000_000, 0.065 / 12.0, 360),
+            (12_345_678, 0.0375 / 26.0, 780),
+            (99_999, 0.01, 7),
+            (120_000, 0.0, 12),
+        ]
+        .iter()
+        {
+            let principal = Money::from_cents(principal);
+            let schedule = super::Schedule::new(principal, rate, payments);
+            let periods = &schedule.periods;
+            assert_eq!(periods.len(), payments as usize);
+            assert_eq!(periods.last().unwrap().balance, Money::ZERO);
+            let principal_paid: Money = periods.iter().map(|period| period.principal).sum();
+            assert_eq!(principal_paid, principal);
+            assert_eq!(schedule.total_cost(), principal + schedule.total_interest());
+            // Only the last payment differs from the rounded regular payment.
+            let payment = periods[0].payment;
+            assert!(periods[..payments as usize - 1]
+                .iter()
+                .all(|period| period.payment == payment));
+            assert!(periods.last().unwrap().payment > Money::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_totals() {
+        let schedule = super::Schedule::new(Money::from_cents(10_000_000), 0.005, 180);
+        assert_eq!(schedule.total_cost().to_string(), "151893.80");
+        assert_eq!(schedule.total_interest().to_string(), "51893.80");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = super::Schedule::new(Money::from_cents(100_000), 0.01, 2).to_csv();
+        let expectation = "period,payment,interest,principal,balance\n\
+                           1,507.51,10.00,497.51,502.49\n\
+                           2,507.51,5.02,502.49,0.00";
+        assert_eq!(csv, expectation);
+    }
+
+    #[test]
+    fn test_to_table() {
+        let table = super::Schedule::new(Money::from_cents(100_000), 0.01, 2).to_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "     1       507.51        10.00       497.51         502.49"
+        );
+        assert_eq!(lines[3], " Total      1015.02        15.02      1000.00");
+    }
+}
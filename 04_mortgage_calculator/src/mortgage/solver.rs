@@ -0,0 +1,137 @@
+use super::money::Money;
+use super::mortgage;
+
+/// Relative precision of the rate found by `rate`.
+const RATE_TOLERANCE: f64 = 1e-12;
+
+/// Largest principal whose payment doesn't exceed `payment`:
+/// P = A * (1 - (1 + r)^-n) / r
+pub fn principal(payment: Money, interest_rate: f64, number_of_payments: i32) -> Money {
+    let payment = payment.to_f64();
+    let principal = if interest_rate == 0.0 {
+        payment * number_of_payments as f64
+    } else {
+        payment * (1.0 - (1.0 + interest_rate).powi(-number_of_payments)) / interest_rate
+    };
+    return Money::from_cents((principal * 100.0 + 1e-6).floor() as i64);
+}
+
+/// Payments needed to pay `principal` off paying at most `payment` each period:
+/// n = -ln(1 - r * P / A) / ln(1 + r)
+/// Like `Schedule`, the regular payment is rounded to the cent and the last one makes up
+/// the difference, so n is lowered while a loan of n - 1 payments still fits `payment`.
+/// None when the payment doesn't even cover the interest.
+pub fn number_of_payments(principal: Money, payment: Money, interest_rate: f64) -> Option<i32> {
+    if payment <= principal.interest(interest_rate) {
+        return None;
+    }
+    let ratio = principal.to_f64() / payment.to_f64();
+    let payments = if interest_rate == 0.0 {
+        ratio
+    } else {
+        -(1.0 - interest_rate * ratio).ln() / (1.0 + interest_rate).ln()
+    };
+    let mut number_of_payments = (payments - 1e-9).ceil() as i32;
+    while number_of_payments > 1
+        && super::payment(principal, interest_rate, number_of_payments - 1) <= payment
+    {
+        number_of_payments -= 1;
+    }
+    return Some(number_of_payments);
+}
+
+/// Interest rate per period at which `principal` is paid off with `number_of_payments`
+/// payments of `payment`. None when the payments add up to less than the principal.
+/// The payment grows with the rate, so the rate is found by bisection in [0, A / P],
+/// since the payment is always above the interest of the first period.
+pub fn rate(principal: Money, payment: Money, number_of_payments: i32) -> Option<f64> {
+    let total = payment.cents() * number_of_payments as i64;
+    if principal <= Money::ZERO || number_of_payments < 1 || total < principal.cents() {
+        return None;
+    }
+    if total == principal.cents() {
+        return Some(0.0);
+    }
+    let (principal, payment) = (principal.to_f64(), payment.to_f64());
+    let mut low = 0.0;
+    let mut high = payment / principal;
+    while high - low > RATE_TOLERANCE * high {
+        let middle = (low + high) / 2.0;
+        if mortgage::mortgage(principal, middle, number_of_payments) > payment {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    return Some((low + high) / 2.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::money::Money;
+
+    const PRINCIPAL: Money = Money::from_cents(10_000_000);
+    const PAYMENT: Money = Money::from_cents(84_386);
+
+    #[test]
+    fn test_principal() {
+        let principal = super::principal(PAYMENT, 0.005, 180);
+        assert_eq!(principal.to_string(), "100000.37");
+        assert!(super::super::payment(principal, 0.005, 180) <= PAYMENT);
+        assert_eq!(
+            super::principal(Money::from_cents(50_000), 0.0, 240).to_string(),
+            "120000.00"
+        );
+    }
+
+    #[test]
+    fn test_number_of_payments() {
+        assert_eq!(
+            super::number_of_payments(PRINCIPAL, PAYMENT, 0.005),
+            Some(180)
+        );
+        assert_eq!(
+            super::number_of_payments(PRINCIPAL, Money::from_cents(100_000), 0.005),
+            Some(139)
+        );
+        assert_eq!(
+            super::number_of_payments(PRINCIPAL, Money::from_cents(50_000), 0.0),
+            Some(200)
+        );
+        // 500.00 only covers the interest of the first period.
+        assert_eq!(
+            super::number_of_payments(PRINCIPAL, Money::from_cents(50_000), 0.005),
+            None
+        );
+        assert_eq!(super::number_of_payments(PRINCIPAL, Money::ZERO, 0.0), None);
+    }
+
+    #[test]
+    fn test_rate() {
+        let rate = super::rate(PRINCIPAL, PAYMENT, 180).unwrap();
+        assert!((rate - 0.005).abs() < 1e-6);
+        let rate = super::rate(PRINCIPAL, Money::from_cents(58_459), 300).unwrap();
+        assert!((rate * 12.0 - 0.05).abs() < 1e-5);
+        assert_eq!(
+            super::rate(Money::from_cents(120_000), Money::from_cents(10_000), 12),
+            Some(0.0)
+        );
+        assert_eq!(super::rate(PRINCIPAL, Money::from_cents(10_000), 12), None);
+    }
+
+    #[test]
+    fn test_solvers_invert_payment() {
+        let cases = [(0.001, 12), (0.0035, 360), (0.01, 60), (0.05 / 12.0, 300)];
+        for &(rate, payments) in cases.iter() {
+            let payment = super::super::payment(PRINCIPAL, rate, payments);
+            let solved = super::rate(PRINCIPAL, payment, payments).unwrap();
+            assert!((solved - rate).abs() / rate < 1e-4);
+            assert_eq!(
+                super::number_of_payments(PRINCIPAL, payment, rate),
+                Some(payments)
+            );
+            let principal = super::principal(payment, rate, payments);
+            assert!((principal - PRINCIPAL).cents().abs() < payments as i64);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+// The coordinate tests use rounded multiples of pi on purpose.
+#[cfg_attr(test, allow(clippy::approx_constant))]
 pub mod coordinate;
 use coordinate::Coordinate;
 
@@ -0,0 +1,243 @@
+extern crate json;
+
+use super::{GeocodeError, Geocoder, HttpClient};
+use std::cell::RefCell;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long cached coordinates are used by default: cities rarely move.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Geocoder remembering the coordinates found by another one in a JSON file, so the same
+/// location is only looked up again once its entry is older than `ttl`.
+pub struct Cache<'a> {
+    inner: Box<dyn Geocoder + 'a>,
+    provider: String,
+    path: String,
+    ttl: Duration,
+    /// Looks every location up again, still saving the results.
+    refresh: bool,
+    /// Normalized location to {"latitude", "longitude", "time"} in seconds since epoch.
+    entries: RefCell<json::JsonValue>,
+}
+
+impl<'a> Cache<'a> {
+    /// Caches the results of `inner`, the geocoder called `provider`, in the file at
+    /// `path`. A missing or unreadable file starts an empty cache.
+    pub fn new(
+        inner: Box<dyn Geocoder + 'a>,
+        provider: &str,
+        path: &str,
+        ttl: Duration,
+        refresh: bool,
+    ) -> Cache<'a> {
+        let entries = match fs::read_to_string(path).map(|text| json::parse(&text)) {
+            Ok(Ok(entries)) if entries.is_object() => entries,
+            _ => json::JsonValue::new_object(),
+        };
+        return Cache {
+            inner,
+            provider: String::from(provider),
+            path: String::from(path),
+            ttl,
+            refresh,
+            entries: RefCell::new(entries),
+        };
+    }
+
+    /// Entries of different providers are kept apart, and locations differing only in
+    /// case or spaces share an entry.
+    fn key(&self, location: &str) -> String {
+        let parts: Vec<String> = location
+            .split(',')
+            .map(|part| {
+                let words: Vec<&str> = part.split_whitespace().collect();
+                return words.join(" ").to_lowercase();
+            })
+            .collect();
+        return format!("{}:{}", self.provider, parts.join(","));
+    }
+
+    fn cached(&self, key: &str, now: u64) -> Option<(f64, f64)> {
+        if self.refresh {
+            return None;
+        }
+        let entries = self.entries.borrow();
+        let entry = &entries[key];
+        let age = now.saturating_sub(entry["time"].as_u64()?);
+        if age >= self.ttl.as_secs() {
+            return None;
+        }
+        return Some((entry["latitude"].as_f64()?, entry["longitude"].as_f64()?));
+    }
+
+    fn store(&self, key: &str, coordinates: (f64, f64), now: u64) {
+        let mut entry = json::JsonValue::new_object();
+        entry["latitude"] = coordinates.0.into();
+        entry["longitude"] = coordinates.1.into();
+        entry["time"] = now.into();
+        self.entries.borrow_mut()[key] = entry;
+        let text = self.entries.borrow().pretty(2);
+        if let Err(error) = fs::write(&self.path, text) {
+            eprintln!("Can't write the cache {}: {}", self.path, error);
+        }
+    }
+}
+
+impl<'a> Geocoder for Cache<'a> {
+    /// Failed lookups aren't cached, so they're tried again next time.
+    fn geocode(&self, location: &str, client: &dyn HttpClient) -> Result<(f64, f64), GeocodeError> {
+        let key = self.key(location);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+        if let Some(coordinates) = self.cached(&key, now) {
+            return Ok(coordinates);
+        }
+        let coordinates = self.inner.geocode(location, client)?;
+        self.store(&key, coordinates, now);
+        return Ok(coordinates);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_clients::NoNetwork;
+    use super::super::{GeocodeError, Geocoder, HttpClient};
+    use super::Cache;
+    use std::cell::Cell;
+    use std::env;
+    use std::fs;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    const TTL: Duration = Duration::from_secs(3600);
+
+    /// Counts the lookups that get past the cache.
+    struct GeocoderSpy {
+        calls: Rc<Cell<u32>>,
+    }
+
+    impl Geocoder for GeocoderSpy {
+        fn geocode(
+            &self,
+            location: &str,
+            _client: &dyn HttpClient,
+        ) -> Result<(f64, f64), GeocodeError> {
+            self.calls.set(self.calls.get() + 1);
+            return match location {
+                "Atlantis" => Err(GeocodeError::NoResults),
+                _ => Ok((48.85341, 2.3488)),
+            };
+        }
+    }
+
+    /// A cache file of its own for each test, removed beforehand.
+    fn cache_path(name: &str) -> String {
+        let path = env::temp_dir().join(format!("distances-{}-{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        return path.to_string_lossy().into_owned();
+    }
+
+    fn spied(path: &str, refresh: bool) -> (Cache<'static>, Rc<Cell<u32>>) {
+        let calls = Rc::new(Cell::new(0));
+        let spy = GeocoderSpy {
+            calls: calls.clone(),
+        };
+        return (Cache::new(Box::new(spy), "spy", path, TTL, refresh), calls);
+    }
+
+    #[test]
+    fn test_repeated_lookups_are_cached() {
+        let path = cache_path("repeated");
+        let (cache, calls) = spied(&path, false);
+        assert_eq!(
+            cache.geocode("Paris,France", &NoNetwork {}),
+            Ok((48.85341, 2.3488))
+        );
+        assert_eq!(
+            cache.geocode("Paris,France", &NoNetwork {}),
+            Ok((48.85341, 2.3488))
+        );
+        assert_eq!(
+            cache.geocode(" paris , FRANCE", &NoNetwork {}),
+            Ok((48.85341, 2.3488))
+        );
+        assert_eq!(calls.get(), 1);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_persists_between_runs() {
+        let path = cache_path("persists");
+        let (first_run, calls) = spied(&path, false);
+        first_run.geocode("Paris,France", &NoNetwork {}).unwrap();
+        assert_eq!(calls.get(), 1);
+        let (second_run, calls) = spied(&path, false);
+        assert_eq!(
+            second_run.geocode("Paris,France", &NoNetwork {}),
+            Ok((48.85341, 2.3488))
+        );
+        assert_eq!(calls.get(), 0);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_expired_entries() {
+        let path = cache_path("expired");
+        let entry = "{\"spy:paris,france\":{\"latitude\":1,\"longitude\":1,\"time\":0}}";
+        fs::write(&path, entry).unwrap();
+        let (cache, calls) = spied(&path, false);
+        assert_eq!(
+            cache.geocode("Paris,France", &NoNetwork {}),
+            Ok((48.85341, 2.3488))
+        );
+        assert_eq!(calls.get(), 1);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_refresh() {
+        let path = cache_path("refresh");
+        let (cache, calls) = spied(&path, true);
+        cache.geocode("Paris,France", &NoNetwork {}).unwrap();
+        cache.geocode("Paris,France", &NoNetwork {}).unwrap();
+        assert_eq!(calls.get(), 2);
+        let (cache, calls) = spied(&path, false);
+        cache.geocode("Paris,France", &NoNetwork {}).unwrap();
+        assert_eq!(calls.get(), 0);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let path = cache_path("errors");
+        let (cache, calls) = spied(&path, false);
+        assert_eq!(
+            cache.geocode("Atlantis", &NoNetwork {}),
+            Err(GeocodeError::NoResults)
+        );
+        assert_eq!(
+            cache.geocode("Atlantis", &NoNetwork {}),
+            Err(GeocodeError::NoResults)
+        );
+        assert_eq!(calls.get(), 2);
+        assert!(fs::metadata(&path).is_err());
+    }
+
+    #[test]
+    fn test_providers_are_kept_apart() {
+        let path = cache_path("providers");
+        let (cache, _) = spied(&path, false);
+        cache.geocode("Paris,France", &NoNetwork {}).unwrap();
+        let calls = Rc::new(Cell::new(0));
+        let spy = GeocoderSpy {
+            calls: calls.clone(),
+        };
+        let other = Cache::new(Box::new(spy), "other", &path, TTL, false);
+        other.geocode("Paris,France", &NoNetwork {}).unwrap();
+        assert_eq!(calls.get(), 1);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// Why a location couldn't be turned into coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeocodeError {
+    /// The request didn't get a response, e.g. no network or the client failed.
+    Transport(String),
+    /// The service answered with an unexpected HTTP or API status code.
+    HttpStatus(u16),
+    /// The response isn't JSON or lacks the expected fields.
+    MalformedJson(String),
+    /// The service didn't find the location.
+    NoResults,
+    /// The service found this many equally good matches for the location.
+    Ambiguous(usize),
+    /// The API key ran out of requests.
+    QuotaExceeded,
+}
+
+impl fmt::Display for GeocodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeocodeError::Transport(error) => write!(f, "The request failed: {}", error),
+            GeocodeError::HttpStatus(status) => {
+                write!(f, "The service answered with status {}", status)
+            }
+            GeocodeError::MalformedJson(error) => {
+                write!(f, "The response is not valid: {}", error)
+            }
+            GeocodeError::NoResults => write!(f, "The location was not found"),
+            GeocodeError::Ambiguous(count) => {
+                write!(f, "The location matches {} places, be more specific", count)
+            }
+            GeocodeError::QuotaExceeded => write!(f, "The API key exceeded its quota"),
+        }
+    }
+}
+
+impl std::error::Error for GeocodeError {}
@@ -0,0 +1,238 @@
+use super::{GeocodeError, Geocoder, HttpClient};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+/// Country names accepted after the city, with their ISO 3166 code used by GeoNames.
+const COUNTRIES: [(&str, &str); 48] = [
+    ("america", "US"),
+    ("argentina", "AR"),
+    ("australia", "AU"),
+    ("austria", "AT"),
+    ("belgium", "BE"),
+    ("brazil", "BR"),
+    ("canada", "CA"),
+    ("chile", "CL"),
+    ("china", "CN"),
+    ("colombia", "CO"),
+    ("denmark", "DK"),
+    ("egypt", "EG"),
+    ("england", "GB"),
+    ("finland", "FI"),
+    ("france", "FR"),
+    ("germany", "DE"),
+    ("great britain", "GB"),
+    ("greece", "GR"),
+    ("holland", "NL"),
+    ("india", "IN"),
+    ("indonesia", "ID"),
+    ("ireland", "IE"),
+    ("italy", "IT"),
+    ("japan", "JP"),
+    ("mexico", "MX"),
+    ("netherlands", "NL"),
+    ("new zealand", "NZ"),
+    ("nigeria", "NG"),
+    ("norway", "NO"),
+    ("peru", "PE"),
+    ("poland", "PL"),
+    ("portugal", "PT"),
+    ("russia", "RU"),
+    ("scotland", "GB"),
+    ("south africa", "ZA"),
+    ("south korea", "KR"),
+    ("spain", "ES"),
+    ("sweden", "SE"),
+    ("switzerland", "CH"),
+    ("thailand", "TH"),
+    ("turkey", "TR"),
+    ("uk", "GB"),
+    ("ukraine", "UA"),
+    ("united kingdom", "GB"),
+    ("united states", "US"),
+    ("usa", "US"),
+    ("venezuela", "VE"),
+    ("wales", "GB"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct City {
+    latitude: f64,
+    longitude: f64,
+    country_code: String,
+    population: u64,
+}
+
+/// Offline geocoder over a GeoNames `cities*.txt` dump, e.g. cities15000.txt from
+/// download.geonames.org/export/dump.
+pub struct Gazetteer {
+    cities: Vec<City>,
+    /// Lowercase name, ASCII name and alternate names to indexes in `cities`.
+    names: HashMap<String, Vec<usize>>,
+}
+
+/// A GeoNames extract of a few major cities, so some lookups work without any download.
+const BUNDLED_CITIES: &str = include_str!("../../data/cities.txt");
+
+impl Gazetteer {
+    pub fn bundled() -> Gazetteer {
+        return Gazetteer::parse(BUNDLED_CITIES).expect("The bundled cities are valid");
+    }
+
+    pub fn load(path: &str) -> io::Result<Gazetteer> {
+        return Gazetteer::parse(&fs::read_to_string(path)?);
+    }
+
+    /// Reads the tab separated GeoNames columns: name (1), ASCII name (2), comma
+    /// separated alternate names (3), latitude (4), longitude (5), country code (8)
+    /// and population (14).
+    pub fn parse(text: &str) -> io::Result<Gazetteer> {
+        let mut gazetteer = Gazetteer {
+            cities: Vec::new(),
+            names: HashMap::new(),
+        };
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let columns: Vec<&str> = line.split('\t').collect();
+            let invalid = || {
+                let message = format!("Invalid GeoNames line {}", number + 1);
+                return io::Error::new(io::ErrorKind::InvalidData, message);
+            };
+            if columns.len() < 15 {
+                return Err(invalid());
+            }
+            let city = City {
+                latitude: columns[4].parse().map_err(|_| invalid())?,
+                longitude: columns[5].parse().map_err(|_| invalid())?,
+                country_code: columns[8].to_uppercase(),
+                population: columns[14].parse().unwrap_or(0),
+            };
+            let index = gazetteer.cities.len();
+            gazetteer.cities.push(city);
+            let alternate_names = columns[3].split(',');
+            for name in [columns[1], columns[2]]
+                .iter()
+                .copied()
+                .chain(alternate_names)
+            {
+                let name = normalize(name);
+                if name.is_empty() {
+                    continue;
+                }
+                let indexes = gazetteer.names.entry(name).or_default();
+                if indexes.last() != Some(&index) {
+                    indexes.push(index);
+                }
+            }
+        }
+        return Ok(gazetteer);
+    }
+}
+
+impl Geocoder for Gazetteer {
+    /// Matches "<city>" or "<city>,<country name or code>" and picks the most populated
+    /// of the cities with that name.
+    fn geocode(
+        &self,
+        location: &str,
+        _client: &dyn HttpClient,
+    ) -> Result<(f64, f64), GeocodeError> {
+        let mut parts = location.splitn(2, ',').map(normalize);
+        let name = parts.next().unwrap_or_default();
+        let country_code = match parts.next() {
+            Some(country) => Some(country_code(&country).ok_or(GeocodeError::NoResults)?),
+            None => None,
+        };
+        let indexes = self.names.get(&name).ok_or(GeocodeError::NoResults)?;
+        let city = indexes
+            .iter()
+            .map(|&index| &self.cities[index])
+            .filter(|city| match &country_code {
+                Some(code) => city.country_code == *code,
+                None => true,
+            })
+            .max_by_key(|city| city.population)
+            .ok_or(GeocodeError::NoResults)?;
+        return Ok((city.latitude, city.longitude));
+    }
+}
+
+fn normalize(name: &str) -> String {
+    return name.trim().to_lowercase();
+}
+
+/// The code of a country given by name or by its two letter code. Names come first, so
+/// "uk" is GB rather than an unknown code.
+fn country_code(country: &str) -> Option<String> {
+    return match COUNTRIES.iter().find(|entry| entry.0 == country) {
+        Some(entry) => Some(String::from(entry.1)),
+        None if country.len() == 2 => Some(country.to_uppercase()),
+        None => None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_clients::NoNetwork;
+    use super::super::{GeocodeError, Geocoder};
+    use super::Gazetteer;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cities.txt");
+
+    fn geocode(location: &str) -> Result<(f64, f64), GeocodeError> {
+        let gazetteer = Gazetteer::load(FIXTURE).unwrap();
+        return gazetteer.geocode(location, &NoNetwork {});
+    }
+
+    #[test]
+    fn test_load() {
+        assert_eq!(Gazetteer::load(FIXTURE).unwrap().cities.len(), 8);
+        assert!(Gazetteer::load("missing.txt").is_err());
+        assert!(Gazetteer::parse("2988507\tParis\tParis").is_err());
+        let invalid_latitude = "1\tA\tA\t\tnorth\t0\tP\tPPL\tFR\t\t\t\t\t\t1";
+        assert!(Gazetteer::parse(invalid_latitude).is_err());
+    }
+
+    #[test]
+    fn test_bundled() {
+        let gazetteer = Gazetteer::bundled();
+        assert_eq!(gazetteer.cities.len(), 16);
+        let geocode = |location: &str| gazetteer.geocode(location, &NoNetwork {});
+        assert_eq!(geocode("Paris,France"), Ok((48.85341, 2.3488)));
+        assert_eq!(geocode("Tokyo"), Ok((35.6895, 139.69171)));
+        assert_eq!(geocode("London"), Ok((51.50853, -0.12574)));
+        assert_eq!(geocode("London,Canada"), Ok((42.98339, -81.23304)));
+        assert_eq!(geocode("Atlantis"), Err(GeocodeError::NoResults));
+    }
+
+    #[test]
+    fn test_country() {
+        assert_eq!(geocode("Paris,France"), Ok((48.85341, 2.3488)));
+        assert_eq!(geocode("Paris, US"), Ok((33.66094, -95.55551)));
+        assert_eq!(geocode("London,England"), Ok((51.50853, -0.12574)));
+        assert_eq!(geocode("London,ca"), Ok((42.98339, -81.23304)));
+        assert_eq!(geocode("London,UK"), Ok((51.50853, -0.12574)));
+        assert_eq!(geocode("Paris,Spain"), Err(GeocodeError::NoResults));
+        assert_eq!(geocode("Paris,Atlantis"), Err(GeocodeError::NoResults));
+    }
+
+    #[test]
+    fn test_ranks_by_population() {
+        assert_eq!(geocode("Paris"), Ok((48.85341, 2.3488)));
+        assert_eq!(geocode("london"), Ok((51.50853, -0.12574)));
+    }
+
+    #[test]
+    fn test_alternate_names() {
+        assert_eq!(geocode("Cologne,Germany"), Ok((50.93333, 6.95)));
+        assert_eq!(geocode("Köln"), Ok((50.93333, 6.95)));
+        assert_eq!(geocode("Koeln"), Ok((50.93333, 6.95)));
+        assert_eq!(geocode("NYC,USA"), Ok((40.71427, -74.00597)));
+        assert_eq!(geocode("Sao Paulo,BR"), Ok((-23.5475, -46.63611)));
+        // Both Londons are called Londres in Spanish.
+        assert_eq!(geocode("Londres"), Ok((51.50853, -0.12574)));
+        assert_eq!(geocode("Atlantis"), Err(GeocodeError::NoResults));
+    }
+}
@@ -0,0 +1,202 @@
+extern crate ureq;
+
+use super::{HttpClient, Response};
+use std::thread;
+use std::time::Duration;
+
+/// Identifies the application, as required by the usage policies of services like
+/// Nominatim, which block the default User-Agent of HTTP libraries.
+pub const USER_AGENT: &str = concat!(
+    "distance_between_cities/",
+    env!("CARGO_PKG_VERSION"),
+    " (distances command line geocoding)"
+);
+
+/// In-process HTTP client that retries failed connections and responses with status
+/// 429 or 5xx, waiting `backoff` before the first retry and twice as long each time.
+pub struct NativeHttpClient {
+    agent: ureq::Agent,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl NativeHttpClient {
+    /// 10 seconds timeout and 3 retries starting after half a second.
+    pub fn new() -> NativeHttpClient {
+        return NativeHttpClient::with_retries(
+            Duration::from_secs(10),
+            3,
+            Duration::from_millis(500),
+        );
+    }
+
+    /// `timeout` applies to the whole of each request, including connecting.
+    pub fn with_retries(timeout: Duration, retries: u32, backoff: Duration) -> NativeHttpClient {
+        return NativeHttpClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout(timeout)
+                .user_agent(USER_AGENT)
+                .build(),
+            retries,
+            backoff,
+        };
+    }
+
+    /// The response, also for error statuses, or the error and whether the request may
+    /// succeed if tried again.
+    fn request(&self, uri: &str) -> Result<Response, (bool, String)> {
+        let response = match self.agent.get(uri).call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(transport)) => {
+                let retryable = matches!(
+                    transport.kind(),
+                    ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io
+                );
+                return Err((retryable, transport.to_string()));
+            }
+        };
+        let status = response.status();
+        return match response.into_string() {
+            Ok(body) => Ok(Response { status, body }),
+            Err(error) => Err((true, error.to_string())),
+        };
+    }
+}
+
+impl HttpClient for NativeHttpClient {
+    fn get(&self, uri: &str) -> Result<Response, String> {
+        let mut attempt = 0;
+        loop {
+            let result = self.request(uri);
+            let retryable = match &result {
+                Ok(response) => response.status == 429 || response.status >= 500,
+                Err((retryable, _)) => *retryable,
+            };
+            if !retryable || attempt == self.retries {
+                return result.map_err(|(_, error)| error);
+            }
+            thread::sleep(self.backoff * 2_u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::HttpClient;
+    use super::NativeHttpClient;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Serves `responses` as (status, body) to one connection each and returns the
+    /// server's address and the request line and headers of each request received.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut lines = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    lines.push(String::from(line.trim_end()));
+                }
+                requests.push(lines);
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            return requests;
+        });
+        return (address, server);
+    }
+
+    fn client(retries: u32) -> NativeHttpClient {
+        return NativeHttpClient::with_retries(
+            Duration::from_secs(5),
+            retries,
+            Duration::from_millis(1),
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let (address, server) = mock_server(vec![(200, "{\"ok\":true}")]);
+        let response = client(0).get(&format!("{}/search?q=Paris%2CFrance", address));
+        let response = response.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{\"ok\":true}");
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0][0], "GET /search?q=Paris%2CFrance HTTP/1.1");
+        let user_agent = format!("user-agent: {}", super::USER_AGENT);
+        assert!(requests[0]
+            .iter()
+            .any(|header| header.to_lowercase() == user_agent.to_lowercase()));
+    }
+
+    #[test]
+    fn test_error_status_is_returned() {
+        let (address, server) = mock_server(vec![(404, "not found")]);
+        let response = client(3).get(&address).unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "not found");
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retries_with_backoff() {
+        let (address, server) = mock_server(vec![(503, ""), (429, ""), (200, "done")]);
+        let response = client(3).get(&address).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "done");
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_gives_up_after_retries() {
+        let (address, server) = mock_server(vec![(500, ""), (502, "")]);
+        let response = client(1).get(&address).unwrap();
+        assert_eq!(response.status, 502);
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            // Accepts the connection but never answers.
+            let connection = listener.accept().unwrap();
+            thread::sleep(Duration::from_millis(500));
+            drop(connection);
+        });
+        let client =
+            NativeHttpClient::with_retries(Duration::from_millis(100), 0, Duration::from_millis(1));
+        assert!(client.get(&address).is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_connection_refused() {
+        let address = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+        assert!(client(1).get(&address).is_err());
+    }
+}
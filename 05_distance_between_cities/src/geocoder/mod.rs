@@ -0,0 +1,184 @@
+extern crate json;
+
+pub mod cache;
+mod error;
+pub mod gazetteer;
+pub mod http;
+pub mod nominatim;
+pub use self::error::GeocodeError;
+
+use crate::map_quest::MapQuest;
+
+/// Status code and body of an HTTP response.
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait HttpClient {
+    /// The response to a GET request, or why no response was received.
+    fn get(&self, uri: &str) -> Result<Response, String>;
+}
+
+/// A service turning a location like "Paris,France" into (latitude, longitude).
+pub trait Geocoder {
+    /// Looks the location up, using `client` for any request to the service.
+    fn geocode(&self, location: &str, client: &dyn HttpClient) -> Result<(f64, f64), GeocodeError>;
+}
+
+/// "offline" and "geonames" are the same gazetteer, over the few major cities bundled
+/// with the binary or over a full GeoNames dump.
+pub const PROVIDERS: [&str; 4] = ["mapquest", "nominatim", "offline", "geonames"];
+
+/// What the providers need to be set up.
+pub struct Settings<'a> {
+    /// MapQuest API key.
+    pub api_key: Option<&'a str>,
+    /// Path of the GeoNames cities file.
+    pub cities: &'a str,
+}
+
+/// The geocoder called `name` in `PROVIDERS`, or why it can't be used.
+pub fn from_name<'a>(
+    name: &str,
+    settings: &Settings<'a>,
+) -> Result<Box<dyn Geocoder + 'a>, String> {
+    return match name {
+        "mapquest" => match settings.api_key {
+            Some(api_key) => Ok(Box::new(MapQuest::new(api_key))),
+            None => Err(String::from(
+                "Get an API key from mapquestapi.com and set MAP_QUEST_API_KEY env var.",
+            )),
+        },
+        "nominatim" => Ok(Box::new(nominatim::Nominatim::new())),
+        "offline" => Ok(Box::new(gazetteer::Gazetteer::bundled())),
+        "geonames" => match gazetteer::Gazetteer::load(settings.cities) {
+            Ok(gazetteer) => Ok(Box::new(gazetteer)),
+            Err(error) => Err(format!("Can't read {}: {}", settings.cities, error)),
+        },
+        _ => Err(format!(
+            "Unknown provider {}, expected one of: {}",
+            name,
+            PROVIDERS.join(", ")
+        )),
+    };
+}
+
+/// Whether the provider called `name` looks locations up over the network, which makes
+/// its results worth caching. The gazetteers are already local files.
+pub fn uses_network(name: &str) -> bool {
+    return matches!(name, "mapquest" | "nominatim");
+}
+
+/// Percent-encodes `text` for a query string, keeping only unreserved characters.
+pub fn encode(text: &str) -> String {
+    return text
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+}
+
+/// The JSON body of a successful response to `uri`.
+pub fn get_json(client: &dyn HttpClient, uri: &str) -> Result<json::JsonValue, GeocodeError> {
+    let response = client.get(uri).map_err(GeocodeError::Transport)?;
+    match response.status {
+        200 => {}
+        429 => return Err(GeocodeError::QuotaExceeded),
+        status => return Err(GeocodeError::HttpStatus(status)),
+    }
+    return json::parse(&response.body)
+        .map_err(|error| GeocodeError::MalformedJson(error.to_string()));
+}
+
+/// HTTP clients standing in for the network in tests.
+#[cfg(test)]
+pub mod test_clients {
+    use super::{HttpClient, Response};
+    use std::cell::RefCell;
+
+    /// Fails the test if a request is made.
+    pub struct NoNetwork {}
+
+    impl HttpClient for NoNetwork {
+        fn get(&self, uri: &str) -> Result<Response, String> {
+            panic!("Unexpected request to {}", uri);
+        }
+    }
+
+    /// Answers every request with the same response or error, remembering the last URI.
+    pub struct ClientSpy {
+        response: Result<(u16, &'static str), &'static str>,
+        pub uri: RefCell<String>,
+    }
+
+    impl ClientSpy {
+        pub fn responding(status: u16, body: &'static str) -> ClientSpy {
+            return ClientSpy {
+                response: Ok((status, body)),
+                uri: RefCell::new(String::new()),
+            };
+        }
+
+        pub fn failing(error: &'static str) -> ClientSpy {
+            return ClientSpy {
+                response: Err(error),
+                uri: RefCell::new(String::new()),
+            };
+        }
+    }
+
+    impl HttpClient for ClientSpy {
+        fn get(&self, uri: &str) -> Result<Response, String> {
+            self.uri.replace(String::from(uri));
+            return match self.response {
+                Ok((status, body)) => Ok(Response {
+                    status,
+                    body: String::from(body),
+                }),
+                Err(error) => Err(String::from(error)),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_encode() {
+        assert_eq!(super::encode("Paris,France"), "Paris%2CFrance");
+        assert_eq!(super::encode("New York, NY"), "New%20York%2C%20NY");
+        assert_eq!(super::encode("Köln&x=1"), "K%C3%B6ln%26x%3D1");
+        assert_eq!(super::encode("a-b_c.d~"), "a-b_c.d~");
+    }
+
+    #[test]
+    fn test_uses_network() {
+        assert!(super::uses_network("mapquest"));
+        assert!(super::uses_network("nominatim"));
+        assert!(!super::uses_network("offline"));
+        assert!(!super::uses_network("geonames"));
+    }
+
+    #[test]
+    fn test_from_name() {
+        let settings = |api_key: Option<&'static str>, cities: &'static str| {
+            return super::Settings { api_key, cities };
+        };
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/cities.txt");
+        let from_name = |name: &str, settings: super::Settings<'static>| {
+            return super::from_name(name, &settings).is_ok();
+        };
+        assert!(from_name("mapquest", settings(Some("key"), "")));
+        assert!(!from_name("mapquest", settings(None, "")));
+        assert!(from_name("nominatim", settings(None, "")));
+        assert!(from_name("offline", settings(None, "")));
+        assert!(from_name("geonames", settings(None, fixture)));
+        assert!(!from_name("geonames", settings(None, "missing.txt")));
+        assert!(!from_name("google", settings(Some("key"), fixture)));
+    }
+}
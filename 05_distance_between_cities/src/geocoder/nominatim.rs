@@ -0,0 +1,82 @@
+use super::{GeocodeError, Geocoder, HttpClient};
+
+/// The OpenStreetMap geocoder, free to use without an API key at up to one request
+/// per second.
+pub struct Nominatim {
+    base_uri: String,
+}
+
+impl Nominatim {
+    pub fn new() -> Nominatim {
+        return Nominatim {
+            base_uri: String::from("https://nominatim.openstreetmap.org/search"),
+        };
+    }
+}
+
+impl Geocoder for Nominatim {
+    fn geocode(&self, location: &str, client: &dyn HttpClient) -> Result<(f64, f64), GeocodeError> {
+        let uri = format!(
+            "{}?format=json&limit=1&q={}",
+            self.base_uri,
+            super::encode(location)
+        );
+        let places = super::get_json(client, &uri)?;
+        if !places.is_array() {
+            return Err(GeocodeError::MalformedJson(String::from(
+                "expected a list of places",
+            )));
+        }
+        if places.is_empty() {
+            return Err(GeocodeError::NoResults);
+        }
+        // Coordinates are strings, e.g. "48.8534951".
+        let coordinate = |name: &str| -> Option<f64> {
+            return places[0][name].as_str()?.parse().ok();
+        };
+        return match (coordinate("lat"), coordinate("lon")) {
+            (Some(latitude), Some(longitude)) => Ok((latitude, longitude)),
+            _ => Err(GeocodeError::MalformedJson(String::from(
+                "missing coordinates",
+            ))),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_clients::ClientSpy;
+    use super::super::{GeocodeError, Geocoder};
+
+    #[test]
+    fn test_geocode() {
+        let body = "[{\"place_id\":88066702,\"lat\":\"48.8534951\",\"lon\":\"2.3483915\",\
+                    \"display_name\":\"Paris, Île-de-France, France\",\"importance\":0.88}]";
+        let client = ClientSpy::responding(200, body);
+        let response = super::Nominatim::new().geocode("Paris,France", &client);
+        assert_eq!(response, Ok((48.8534951, 2.3483915)));
+        assert_eq!(
+            *client.uri.borrow(),
+            "https://nominatim.openstreetmap.org/search?format=json&limit=1&q=Paris%2CFrance"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let geocode = |status: u16, body: &'static str| {
+            return super::Nominatim::new()
+                .geocode("Atlantis", &ClientSpy::responding(status, body));
+        };
+        assert_eq!(geocode(200, "[]"), Err(GeocodeError::NoResults));
+        assert_eq!(geocode(429, ""), Err(GeocodeError::QuotaExceeded));
+        assert_eq!(geocode(503, ""), Err(GeocodeError::HttpStatus(503)));
+        assert!(matches!(
+            geocode(200, "{\"error\":\"bad\"}"),
+            Err(GeocodeError::MalformedJson(_))
+        ));
+        assert!(matches!(
+            geocode(200, "[{\"lat\":\"north\",\"lon\":\"2.3\"}]"),
+            Err(GeocodeError::MalformedJson(_))
+        ));
+    }
+}
@@ -1,57 +1,89 @@
+#![allow(clippy::needless_return)]
 extern crate json;
+extern crate utilities;
 use std::env;
-use std::process::Command;
+use std::process;
 mod geo_location;
 use geo_location::coordinate::Coordinate;
+mod geocoder;
 mod map_quest;
-use map_quest::MapQuest;
+use geocoder::cache::{self, Cache};
+use geocoder::http::NativeHttpClient;
+use geocoder::{GeocodeError, Geocoder};
+use utilities::arguments;
 
+/// Usage: `distances [--provider mapquest|nominatim|offline|geonames] [--cities <file>]
+/// [--cache <file>] [--no-cache] [--refresh]`, MapQuest by default. Offline knows a few
+/// major cities, GeoNames looks cities up in a cities*.txt dump from
+/// download.geonames.org/export/dump, cities15000.txt by default. Coordinates found
+/// by MapQuest and Nominatim are cached for 30 days in ~/.distances_cache.json by default,
+/// `--refresh` looks them up again and `--no-cache` neither reads nor writes the cache.
 fn main() {
-    match env::var("MAP_QUEST_API_KEY") {
-        Ok(key) => start(&key),
-        Err(error) => println!(
-            "Get an API key from mapquestapi.com and set MAP_QUEST_API_KEY env var. ({})",
-            error
-        ),
+    let args: Vec<String> = env::args().skip(1).collect();
+    let provider = match arguments::flag_string(&args, "--provider") {
+        Some(provider) => provider.as_str(),
+        None => "mapquest",
+    };
+    let api_key = env::var("MAP_QUEST_API_KEY").ok();
+    let settings = geocoder::Settings {
+        api_key: api_key.as_deref(),
+        cities: match arguments::flag_string(&args, "--cities") {
+            Some(cities) => cities.as_str(),
+            None => "cities15000.txt",
+        },
+    };
+    let geocoder = match geocoder::from_name(provider, &settings) {
+        Ok(geocoder) => geocoder,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+    let cache_path = match arguments::flag_string(&args, "--cache") {
+        Some(path) => path.clone(),
+        None => default_cache_path(),
+    };
+    let use_cache = geocoder::uses_network(provider) && !arguments::has_flag(&args, "--no-cache");
+    let geocoder: Box<dyn Geocoder> = if !use_cache {
+        geocoder
+    } else {
+        Box::new(Cache::new(
+            geocoder,
+            provider,
+            &cache_path,
+            cache::DEFAULT_TTL,
+            arguments::has_flag(&args, "--refresh"),
+        ))
+    };
+    if let Err(error) = start(geocoder.as_ref()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn default_cache_path() -> String {
+    return match env::var("HOME") {
+        Ok(home) => format!("{}/.distances_cache.json", home),
+        Err(_) => String::from(".distances_cache.json"),
     };
 }
 
-fn start(api_key: &str) {
-    let map_quest = MapQuest::new(api_key);
-    let paris = map_quest.coordinates("Paris,France");
-    let london = map_quest.coordinates("London,England");
+fn start(geocoder: &dyn Geocoder) -> Result<(), GeocodeError> {
+    let paris = coordinates(geocoder, "Paris,France")?;
+    let london = coordinates(geocoder, "London,England")?;
 
     println!("Paris: {}", paris);
     println!("London: {}", london);
     let distance = geo_location::calculate_distance(paris, london);
     println!("Distance: {}", distance);
+    return Ok(());
 }
 
-impl<'a> MapQuest<'a> {
-    fn coordinates(&self, location: &str) -> Coordinate {
-        let client = CurlHtmlClient::new();
-        let coordinates_tuple = self.get_coordinates(location, &client);
-        return Coordinate {
-            latitude: coordinates_tuple.0,
-            longitude: coordinates_tuple.1,
-        };
-    }
-}
-
-struct CurlHtmlClient {}
-
-impl CurlHtmlClient {
-    fn new() -> CurlHtmlClient {
-        return CurlHtmlClient {};
-    }
-}
-
-impl map_quest::HttpClient for CurlHtmlClient {
-    fn get(&self, uri: &str) -> String {
-        let output = Command::new("curl")
-            .arg(uri)
-            .output()
-            .expect("Failed to execute command");
-        return String::from_utf8(output.stdout).expect("ERROR!!");
-    }
+fn coordinates(geocoder: &dyn Geocoder, location: &str) -> Result<Coordinate, GeocodeError> {
+    let client = NativeHttpClient::new();
+    let coordinates_tuple = geocoder.geocode(location, &client)?;
+    return Ok(Coordinate {
+        latitude: coordinates_tuple.0,
+        longitude: coordinates_tuple.1,
+    });
 }
@@ -1,71 +1,183 @@
 extern crate json;
 
-pub trait HttpClient {
-    fn get(&self, uri: &str) -> String;
-}
+use crate::geocoder::{self, GeocodeError, Geocoder, HttpClient};
 
 pub struct MapQuest<'a> {
     api_key: &'a str,
 }
 
 impl<'a> MapQuest<'a> {
-    pub fn new(api_key: &str) -> MapQuest {
+    pub fn new(api_key: &str) -> MapQuest<'_> {
         return MapQuest { api_key };
     }
 }
 
 impl<'a> MapQuest<'a> {
-    pub fn get_coordinates(&self, location: &str, client: &impl HttpClient) -> (f64, f64) {
-        let response = self.get_location_info(location, client);
-        let latlng = &response["results"][0]["locations"][0]["latLng"];
-        return (
-            latlng["lat"].as_f64().unwrap(),
-            latlng["lng"].as_f64().unwrap(),
-        );
+    pub fn get_coordinates(
+        &self,
+        location: &str,
+        client: &dyn HttpClient,
+    ) -> Result<(f64, f64), GeocodeError> {
+        let response = self.get_location_info(location, client)?;
+        let locations = &response["results"][0]["locations"];
+        if !locations.is_array() {
+            return Err(GeocodeError::MalformedJson(String::from("missing results")));
+        }
+        if locations.is_empty() {
+            return Err(GeocodeError::NoResults);
+        }
+        // Locations are sorted by quality, so the first one is the best match
+        // unless the next one is as good.
+        let best_quality = &locations[0]["geocodeQualityCode"];
+        let equally_good = locations
+            .members()
+            .take_while(|location| &location["geocodeQualityCode"] == best_quality)
+            .count();
+        if equally_good > 1 {
+            return Err(GeocodeError::Ambiguous(equally_good));
+        }
+        let latlng = &locations[0]["latLng"];
+        return match (latlng["lat"].as_f64(), latlng["lng"].as_f64()) {
+            (Some(latitude), Some(longitude)) => Ok((latitude, longitude)),
+            _ => Err(GeocodeError::MalformedJson(String::from(
+                "missing coordinates",
+            ))),
+        };
     }
 
-    fn get_location_info(&self, location: &str, client: &impl HttpClient) -> json::JsonValue {
+    fn get_location_info(
+        &self,
+        location: &str,
+        client: &dyn HttpClient,
+    ) -> Result<json::JsonValue, GeocodeError> {
         let base_uri = "http://www.mapquestapi.com/geocoding/v1/address";
-        let uri = format!("{}?key={}&location={}", base_uri, self.api_key, location);
-        let response = client.get(&uri);
-        return json::parse(&response).unwrap();
+        let uri = format!(
+            "{}?key={}&location={}",
+            base_uri,
+            geocoder::encode(self.api_key),
+            geocoder::encode(location)
+        );
+        let info = geocoder::get_json(client, &uri)?;
+        // MapQuest reports errors like an invalid key with status 200 and a status code
+        // in the body.
+        return match info["info"]["statuscode"].as_u16() {
+            None | Some(0) => Ok(info),
+            Some(403) if mentions_quota(&info["info"]["messages"]) => {
+                Err(GeocodeError::QuotaExceeded)
+            }
+            Some(status) => Err(GeocodeError::HttpStatus(status)),
+        };
     }
 }
 
+impl<'a> Geocoder for MapQuest<'a> {
+    fn geocode(&self, location: &str, client: &dyn HttpClient) -> Result<(f64, f64), GeocodeError> {
+        return self.get_coordinates(location, client);
+    }
+}
+
+fn mentions_quota(messages: &json::JsonValue) -> bool {
+    return messages.members().any(|message| {
+        let message = message.as_str().unwrap_or("").to_lowercase();
+        return message.contains("limit") || message.contains("quota");
+    });
+}
+
 #[cfg(test)]
 mod tests {
     extern crate json;
-    struct ClientSpy;
+    use crate::geocoder::test_clients::ClientSpy;
+    use crate::geocoder::GeocodeError;
 
-    impl ClientSpy {
-        const JSON_STRING: &'static str =
-            "{\"results\":[{\"locations\":[{\"latLng\":{\"lat\":0.5,\"lng\":-0.5}}]}]}";
+    const JSON_STRING: &str =
+        "{\"results\":[{\"locations\":[{\"latLng\":{\"lat\":0.5,\"lng\":-0.5}}]}]}";
 
-        fn new() -> ClientSpy {
-            return ClientSpy {};
-        }
-    }
-
-    impl super::HttpClient for ClientSpy {
-        fn get(&self, _uri: &str) -> String {
-            return String::from(ClientSpy::JSON_STRING);
-        }
+    fn coordinates(client: ClientSpy) -> Result<(f64, f64), GeocodeError> {
+        return super::MapQuest::new("key").get_coordinates("location", &client);
     }
 
     #[test]
     fn test_get_location_info() {
-        let client = ClientSpy::new();
+        let client = ClientSpy::responding(200, JSON_STRING);
         let map_quest = super::MapQuest::new("key");
         let response = map_quest.get_location_info("location", &client);
-        let expectation = json::parse(ClientSpy::JSON_STRING).unwrap();
-        assert_eq!(response, expectation);
+        let expectation = json::parse(JSON_STRING).unwrap();
+        assert_eq!(response, Ok(expectation));
     }
 
     #[test]
     fn test_get_coordinates() {
-        let client = ClientSpy::new();
+        let client = ClientSpy::responding(200, JSON_STRING);
         let map_quest = super::MapQuest::new("key");
         let response = map_quest.get_coordinates("location", &client);
-        assert_eq!(response, (0.5, -0.5));
+        assert_eq!(response, Ok((0.5, -0.5)));
+    }
+
+    #[test]
+    fn test_transport_error() {
+        let response = coordinates(ClientSpy::failing("connection refused"));
+        assert_eq!(
+            response,
+            Err(GeocodeError::Transport(String::from("connection refused")))
+        );
+    }
+
+    #[test]
+    fn test_http_status_error() {
+        let response = coordinates(ClientSpy::responding(401, "The AppKey is invalid"));
+        assert_eq!(response, Err(GeocodeError::HttpStatus(401)));
+    }
+
+    #[test]
+    fn test_api_status_error() {
+        let body = "{\"info\":{\"statuscode\":400,\"messages\":[\"Illegal argument\"]}}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert_eq!(response, Err(GeocodeError::HttpStatus(400)));
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        let response = coordinates(ClientSpy::responding(200, "<html>"));
+        assert!(matches!(response, Err(GeocodeError::MalformedJson(_))));
+        let response = coordinates(ClientSpy::responding(200, "{}"));
+        assert!(matches!(response, Err(GeocodeError::MalformedJson(_))));
+        let body = "{\"results\":[{\"locations\":[{\"latLng\":{\"lat\":\"north\"}}]}]}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert!(matches!(response, Err(GeocodeError::MalformedJson(_))));
+    }
+
+    #[test]
+    fn test_no_results() {
+        let body = "{\"info\":{\"statuscode\":0},\"results\":[{\"locations\":[]}]}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert_eq!(response, Err(GeocodeError::NoResults));
+    }
+
+    #[test]
+    fn test_ambiguous_result() {
+        let body = "{\"results\":[{\"locations\":[\
+                    {\"geocodeQualityCode\":\"A5XAX\",\"latLng\":{\"lat\":1,\"lng\":1}},\
+                    {\"geocodeQualityCode\":\"A5XAX\",\"latLng\":{\"lat\":2,\"lng\":2}},\
+                    {\"geocodeQualityCode\":\"A3XAX\",\"latLng\":{\"lat\":3,\"lng\":3}}]}]}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert_eq!(response, Err(GeocodeError::Ambiguous(2)));
+    }
+
+    #[test]
+    fn test_best_of_several_results() {
+        let body = "{\"results\":[{\"locations\":[\
+                    {\"geocodeQualityCode\":\"A5XAX\",\"latLng\":{\"lat\":1,\"lng\":1}},\
+                    {\"geocodeQualityCode\":\"A3XAX\",\"latLng\":{\"lat\":2,\"lng\":2}}]}]}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert_eq!(response, Ok((1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_quota_exceeded() {
+        let response = coordinates(ClientSpy::responding(429, ""));
+        assert_eq!(response, Err(GeocodeError::QuotaExceeded));
+        let body = "{\"info\":{\"statuscode\":403,\"messages\":[\"This key has exceeded its monthly transaction limit\"]}}";
+        let response = coordinates(ClientSpy::responding(200, body));
+        assert_eq!(response, Err(GeocodeError::QuotaExceeded));
     }
 }
@@ -0,0 +1,63 @@
+use super::series::{Series, Term};
+use super::Constant;
+use num_bigint::BigInt;
+
+/// Each term of the series is about 4^5 = 1024 times smaller than the previous one.
+const DIGITS_PER_TERM: f64 = 3.010_299_956_639_812;
+
+/// Apéry's constant ζ(3).
+pub struct Apery;
+
+/// Amdeberhan–Zeilberger series:
+/// ζ(3) = 1/64 Σ (-1)^k (k!)^10 (205k² + 250k + 77) / ((2k + 1)!)^5
+/// https://en.wikipedia.org/wiki/Apéry%27s_constant
+pub struct AmdeberhanZeilberger;
+
+impl Series for AmdeberhanZeilberger {
+    /// Consecutive terms have a ratio of -k^10 / ((2k)(2k + 1))^5 = -k^5 / (32 (2k + 1)^5).
+    fn term(&self, k: u64) -> Term {
+        let (p, q) = match k {
+            0 => (BigInt::from(1), BigInt::from(1)),
+            _ => (-BigInt::from(k).pow(5), 32 * BigInt::from(2 * k + 1).pow(5)),
+        };
+        let k = BigInt::from(k);
+        return Term {
+            p,
+            q,
+            a: 205 * &k * &k + 250 * &k + 77,
+            b: BigInt::from(1),
+        };
+    }
+
+    fn number_of_terms(&self, digits: u64) -> u64 {
+        return (digits as f64 / DIGITS_PER_TERM) as u64 + 1;
+    }
+}
+
+impl Constant for Apery {
+    fn name(&self) -> &'static str {
+        return "zeta3";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "ζ(3)";
+    }
+
+    fn scaled(&self, digits: u64) -> BigInt {
+        return AmdeberhanZeilberger.sum(digits).scaled(digits) / 64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Constant;
+
+    const ZETA3_1000: &str = include_str!("../fixtures/zeta3_1000.txt");
+
+    #[test]
+    fn test_calculate() {
+        assert_eq!(super::Apery.calculate(0), "1");
+        assert_eq!(super::Apery.calculate(5), "1.20205");
+        assert_eq!(super::Apery.calculate(1000), ZETA3_1000);
+    }
+}
@@ -0,0 +1,90 @@
+use super::series::{Series, Term};
+use super::Constant;
+use num_bigint::BigInt;
+
+pub struct E;
+
+/// Calculate e with the given number of decimal digits.
+pub fn calculate(number_of_decimals: u64) -> String {
+    return E.calculate(number_of_decimals);
+}
+
+/// e = 1/0! + 1/1! + 1/2! + 1/3! + 1/4! + ... 1/N!
+pub struct Exponential;
+
+impl Series for Exponential {
+    fn term(&self, k: u64) -> Term {
+        return Term {
+            p: BigInt::from(1),
+            q: BigInt::from(k.max(1)),
+            a: BigInt::from(1),
+            b: BigInt::from(1),
+        };
+    }
+
+    /// Smallest N whose truncation error, Σ 1/k! for k > N, is below 10^-digits.
+    /// The error is bounded by 1 / (N! * N), so N grows until log10(N! * N) > digits.
+    fn number_of_terms(&self, digits: u64) -> u64 {
+        let mut log10_factorial = 0_f64;
+        let mut n = 1_u64;
+        while log10_factorial + (n as f64).log10() <= digits as f64 {
+            n += 1;
+            log10_factorial += (n as f64).log10();
+        }
+        return n + 1;
+    }
+}
+
+impl Constant for E {
+    fn name(&self) -> &'static str {
+        return "e";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "e";
+    }
+
+    fn scaled(&self, digits: u64) -> BigInt {
+        return Exponential.sum(digits).scaled(digits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::series::Series;
+
+    const E_1000: &str = include_str!("../fixtures/e_1000.txt");
+    const E_20000_TAIL: &str = "2748454826";
+
+    #[test]
+    fn test_calculate() {
+        // e = 2.71828182845904523536028747135266249
+        assert_eq!(super::calculate(0), "2");
+        assert_eq!(super::calculate(1), "2.7");
+        assert_eq!(super::calculate(2), "2.71");
+        assert_eq!(super::calculate(5), "2.71828");
+        assert_eq!(super::calculate(10), "2.7182818284");
+        assert_eq!(super::calculate(15), "2.718281828459045");
+        assert_eq!(
+            super::calculate(35),
+            "2.71828182845904523536028747135266249"
+        );
+        assert_eq!(super::calculate(1000), E_1000);
+    }
+
+    #[test]
+    fn test_calculate_many_decimals() {
+        let e = super::calculate(20_000);
+        assert_eq!(e.len(), 20_002);
+        assert_eq!(e[..1002], *E_1000);
+        // Decimals 19,991 to 20,000 of e
+        assert_eq!(&e[19_992..], E_20000_TAIL);
+    }
+
+    #[test]
+    fn test_number_of_terms() {
+        // Terms 0 to 14: 1 / (13! * 13) ≈ 1.2e-11 while 1 / (14! * 14) ≈ 8.2e-13
+        assert_eq!(super::Exponential.number_of_terms(11), 15);
+        assert_eq!(super::Exponential.number_of_terms(12), 15);
+    }
+}
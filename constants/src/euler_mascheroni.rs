@@ -0,0 +1,69 @@
+use super::ln2::Ln2;
+use super::{Constant, GUARD_DIGITS};
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+/// Euler–Mascheroni constant γ.
+pub struct EulerMascheroni;
+
+impl Constant for EulerMascheroni {
+    fn name(&self) -> &'static str {
+        return "gamma";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "γ";
+    }
+
+    /// Brent–McMillan algorithm: γ ≈ U / V with
+    /// U = Σ (n^k / k!)² (H(k) - ln n) and V = Σ (n^k / k!)²,
+    /// whose error is below e^(-4n). n is a power of two so ln n = m ln 2.
+    /// https://en.wikipedia.org/wiki/Euler%27s_constant
+    fn scaled(&self, digits: u64) -> BigInt {
+        // The intermediate sums lose a few digits to truncation on each of their many terms.
+        let working_digits = digits + GUARD_DIGITS;
+        let one = BigInt::from(10).pow(working_digits as u32);
+        let m = power_of_two_exponent(working_digits);
+        let n_squared = 1_u64 << (2 * m);
+        let mut a = -(Ln2.scaled(working_digits) * m);
+        let mut b = one;
+        let mut u = a.clone();
+        let mut v = b.clone();
+        let mut k = 1_u64;
+        while !(a.is_zero() && b.is_zero()) {
+            b = b * n_squared / (k * k);
+            a = (a * n_squared / k + &b) / k;
+            u += &a;
+            v += &b;
+            k += 1;
+        }
+        return u * BigInt::from(10).pow(digits as u32) / v;
+    }
+}
+
+/// Smallest m for which n = 2^m makes e^(-4n) smaller than 10^-digits.
+fn power_of_two_exponent(digits: u64) -> u64 {
+    let n = digits as f64 * std::f64::consts::LN_10 / 4.0;
+    return (n.log2().ceil() as u64).max(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Constant;
+
+    const GAMMA_1000: &str = include_str!("../fixtures/gamma_1000.txt");
+
+    #[test]
+    fn test_power_of_two_exponent() {
+        // 1000 digits need n ≥ 575.6
+        assert_eq!(super::power_of_two_exponent(1000), 10);
+        assert_eq!(super::power_of_two_exponent(1), 1);
+    }
+
+    #[test]
+    fn test_calculate() {
+        assert_eq!(super::EulerMascheroni.calculate(0), "0");
+        assert_eq!(super::EulerMascheroni.calculate(5), "0.57721");
+        assert_eq!(super::EulerMascheroni.calculate(1000), GAMMA_1000);
+    }
+}
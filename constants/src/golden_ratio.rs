@@ -0,0 +1,35 @@
+use super::Constant;
+use num_bigint::BigInt;
+
+pub struct GoldenRatio;
+
+impl Constant for GoldenRatio {
+    fn name(&self) -> &'static str {
+        return "phi";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "φ";
+    }
+
+    /// φ = (1 + √5) / 2
+    fn scaled(&self, digits: u64) -> BigInt {
+        let one = BigInt::from(10).pow(digits as u32);
+        let sqrt_5 = (BigInt::from(5) * &one * &one).sqrt();
+        return (one + sqrt_5) / 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Constant;
+
+    const PHI_1000: &str = include_str!("../fixtures/phi_1000.txt");
+
+    #[test]
+    fn test_calculate() {
+        assert_eq!(super::GoldenRatio.calculate(0), "1");
+        assert_eq!(super::GoldenRatio.calculate(5), "1.61803");
+        assert_eq!(super::GoldenRatio.calculate(1000), PHI_1000);
+    }
+}
@@ -0,0 +1,104 @@
+#![allow(clippy::needless_return)]
+
+extern crate num_bigint;
+extern crate num_traits;
+extern crate utilities;
+
+pub mod apery;
+pub mod e;
+pub mod euler_mascheroni;
+pub mod golden_ratio;
+pub mod ln2;
+pub mod pi;
+pub mod series;
+pub mod sqrt2;
+
+use num_bigint::BigInt;
+use utilities::digits_format::GUARD_DIGITS;
+
+/// A mathematical constant whose decimal digits can be calculated to any precision.
+pub trait Constant {
+    /// Name used to pick the constant from the command line, e.g. "pi".
+    fn name(&self) -> &'static str;
+
+    /// Symbol used when printing the constant, e.g. "π".
+    fn symbol(&self) -> &'static str;
+
+    /// The constant scaled by 10^digits, truncated to an integer.
+    fn scaled(&self, digits: u64) -> BigInt;
+
+    /// Decimal representation of the constant with the given number of decimal digits.
+    fn calculate(&self, number_of_decimals: u64) -> String {
+        let digits = number_of_decimals + GUARD_DIGITS;
+        return convert_to_string(self.scaled(digits), digits, number_of_decimals);
+    }
+}
+
+/// Every constant in the library.
+pub fn all() -> Vec<Box<dyn Constant>> {
+    return vec![
+        Box::new(pi::Pi),
+        Box::new(e::E),
+        Box::new(sqrt2::Sqrt2),
+        Box::new(golden_ratio::GoldenRatio),
+        Box::new(ln2::Ln2),
+        Box::new(euler_mascheroni::EulerMascheroni),
+        Box::new(apery::Apery),
+    ];
+}
+
+pub fn find(name: &str) -> Option<Box<dyn Constant>> {
+    return all().into_iter().find(|constant| constant.name() == name);
+}
+
+/// `scaled` holds a non negative constant scaled by 10^digits. Keeps
+/// `number_of_decimals` of its `digits` decimal digits.
+fn convert_to_string(scaled: BigInt, digits: u64, number_of_decimals: u64) -> String {
+    let string = scaled.to_string();
+    // Constants below 1 are missing the leading zero of their integer part.
+    let padding = (digits as usize + 1).saturating_sub(string.len());
+    let string = "0".repeat(padding) + &string;
+    let integer_length = string.len() - digits as usize;
+    return match number_of_decimals {
+        0 => string[..integer_length].to_string(),
+        _ => format!(
+            "{}.{}",
+            &string[..integer_length],
+            &string[integer_length..integer_length + number_of_decimals as usize]
+        ),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_convert_to_string() {
+        assert_eq!(super::convert_to_string(BigInt::from(31415), 4, 2), "3.14");
+        assert_eq!(super::convert_to_string(BigInt::from(31415), 4, 0), "3");
+        assert_eq!(super::convert_to_string(BigInt::from(6931), 4, 3), "0.693");
+        assert_eq!(super::convert_to_string(BigInt::from(12), 4, 4), "0.0012");
+        assert_eq!(super::convert_to_string(BigInt::from(123456), 4, 1), "12.3");
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            super::find("pi").map(|constant| constant.symbol()),
+            Some("π")
+        );
+        assert!(super::find("tau").is_none());
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names: Vec<&str> = super::all()
+            .iter()
+            .map(|constant| constant.name())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), super::all().len());
+    }
+}
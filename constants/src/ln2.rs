@@ -0,0 +1,55 @@
+use super::series::{Series, Term};
+use super::Constant;
+use num_bigint::BigInt;
+
+/// Each term of the series is 9 times smaller than the previous one.
+const DIGITS_PER_TERM: f64 = 0.954_242_509_439_324_9;
+
+pub struct Ln2;
+
+/// ln 2 = 2 artanh(1/3) = 2 Σ 1 / ((2k + 1) 3^(2k + 1))
+/// https://en.wikipedia.org/wiki/Natural_logarithm_of_2
+pub struct AreaHyperbolicTangent;
+
+impl Series for AreaHyperbolicTangent {
+    fn term(&self, k: u64) -> Term {
+        return Term {
+            p: BigInt::from(1),
+            q: BigInt::from(if k == 0 { 3 } else { 9 }),
+            a: BigInt::from(1),
+            b: BigInt::from(2 * k + 1),
+        };
+    }
+
+    fn number_of_terms(&self, digits: u64) -> u64 {
+        return (digits as f64 / DIGITS_PER_TERM) as u64 + 1;
+    }
+}
+
+impl Constant for Ln2 {
+    fn name(&self) -> &'static str {
+        return "ln2";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "ln 2";
+    }
+
+    fn scaled(&self, digits: u64) -> BigInt {
+        return 2 * AreaHyperbolicTangent.sum(digits).scaled(digits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Constant;
+
+    const LN2_1000: &str = include_str!("../fixtures/ln2_1000.txt");
+
+    #[test]
+    fn test_calculate() {
+        assert_eq!(super::Ln2.calculate(0), "0");
+        assert_eq!(super::Ln2.calculate(5), "0.69314");
+        assert_eq!(super::Ln2.calculate(1000), LN2_1000);
+    }
+}
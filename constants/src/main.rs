@@ -0,0 +1,50 @@
+#![allow(clippy::needless_return)]
+
+extern crate constants;
+extern crate utilities;
+use std::env;
+use utilities::arguments;
+use utilities::digits_format::DigitFormat;
+
+/// Usage: `constants --constant <name> --digits <n> [--radix <2-36>] [--group <n>]
+/// [--line <n>] [--output <file>]` or `constants --list`
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if arguments::has_flag(&args, "--list") {
+        return list_constants();
+    }
+    let constant = match arguments::flag_string(&args, "--constant") {
+        Some(name) => constants::find(name),
+        None => None,
+    };
+    let constant = match constant {
+        Some(constant) => constant,
+        None => {
+            eprintln!("Choose a constant with --constant <name>.");
+            return list_constants();
+        }
+    };
+    let number_of_digits = match arguments::flag_value(&args, "--digits") {
+        Some(number_of_digits) => number_of_digits,
+        None => {
+            let title = format!("Enter the number of decimals of {}:", constant.symbol());
+            utilities::read_number_input::read_non_negative_number(&title) as u64
+        }
+    };
+    let format = match DigitFormat::from_args(&args) {
+        Ok(format) => format,
+        Err(error) => return eprintln!("{}", error),
+    };
+    let result = constant.calculate(format.decimals_needed(number_of_digits));
+    let formatted = format.format(&result, number_of_digits);
+    let title = format!("{} = ", constant.symbol());
+    if let Err(error) = format.write(&title, &formatted) {
+        eprintln!("Could not write the digits: {}", error);
+    }
+}
+
+fn list_constants() {
+    for constant in constants::all() {
+        println!("{:<6} {}", constant.name(), constant.symbol());
+    }
+}
@@ -0,0 +1,219 @@
+use super::super::series::Split;
+use super::Node;
+use num_bigint::BigInt;
+use std::convert::TryInto;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"PICP";
+/// Version 2 stores the B of every split, and the sign of the series terms in P.
+const VERSION: u32 = 2;
+
+/// Intermediate state of a Chudnovsky run: the binary splitting results that
+/// are already known, each one covering a contiguous range of series terms.
+///
+/// File layout (integers are little endian):
+/// magic "PICP" | version u32 | digits u64 | terms u64 | chunks u64 | node count u64 |
+/// nodes: (a u64 | b u64 | P | Q | B | T) where each big integer is (length u64 | signed bytes) |
+/// FNV-1a 64 checksum of everything before it.
+#[derive(Debug, PartialEq)]
+pub struct Checkpoint {
+    pub digits: u64,
+    pub terms: u64,
+    pub chunks: u64,
+    pub nodes: Vec<Node>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> io::Result<Checkpoint> {
+        return decode(&fs::read(path)?);
+    }
+}
+
+/// Writes the checkpoint to a temporary file first, so a run killed while
+/// saving never leaves a truncated checkpoint behind.
+pub fn save(path: &Path, digits: u64, terms: u64, chunks: u64, nodes: &[&Node]) -> io::Result<()> {
+    let bytes = encode(digits, terms, chunks, nodes);
+    let temporary_path = path.with_extension("tmp");
+    fs::write(&temporary_path, bytes)?;
+    return fs::rename(temporary_path, path);
+}
+
+fn encode(digits: u64, terms: u64, chunks: u64, nodes: &[&Node]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    for value in &[digits, terms, chunks, nodes.len() as u64] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for ((a, b), split) in nodes {
+        bytes.extend_from_slice(&a.to_le_bytes());
+        bytes.extend_from_slice(&b.to_le_bytes());
+        for number in &[&split.p, &split.q, &split.b, &split.t] {
+            let number_bytes = number.to_signed_bytes_le();
+            bytes.extend_from_slice(&(number_bytes.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&number_bytes);
+        }
+    }
+    let checksum = fnv1a(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    return bytes;
+}
+
+fn decode(bytes: &[u8]) -> io::Result<Checkpoint> {
+    if bytes.len() < MAGIC.len() + 8 || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(invalid_data("Not a pi checkpoint file"));
+    }
+    let (content, checksum) = bytes.split_at(bytes.len() - 8);
+    if fnv1a(content) != u64::from_le_bytes(checksum.try_into().unwrap()) {
+        return Err(invalid_data("Checkpoint checksum mismatch"));
+    }
+    let mut reader = Reader {
+        bytes: content,
+        position: MAGIC.len(),
+    };
+    let version = reader.u32()?;
+    if version != VERSION {
+        return Err(invalid_data(&format!(
+            "Unsupported checkpoint version {}",
+            version
+        )));
+    }
+    let digits = reader.u64()?;
+    let terms = reader.u64()?;
+    let chunks = reader.u64()?;
+    let count = reader.u64()?;
+    let mut nodes = Vec::new();
+    for _ in 0..count {
+        let range = (reader.u64()?, reader.u64()?);
+        let split = Split {
+            p: reader.big_int()?,
+            q: reader.big_int()?,
+            b: reader.big_int()?,
+            t: reader.big_int()?,
+        };
+        nodes.push((range, split));
+    }
+    return Ok(Checkpoint {
+        digits,
+        terms,
+        chunks,
+        nodes,
+    });
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() - self.position < length {
+            return Err(invalid_data("Checkpoint is truncated"));
+        }
+        let slice = &self.bytes[self.position..self.position + length];
+        self.position += length;
+        return Ok(slice);
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        return Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()));
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        return Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()));
+    }
+
+    fn big_int(&mut self) -> io::Result<BigInt> {
+        let length = self.u64()? as usize;
+        return Ok(BigInt::from_signed_bytes_le(self.take(length)?));
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, message);
+}
+
+/// 64 bit Fowler–Noll–Vo (FNV-1a) hash.
+/// https://en.wikipedia.org/wiki/Fowler–Noll–Vo_hash_function
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    return hash;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::series::{binary_split, Split};
+    use super::super::Chudnovsky;
+    use num_bigint::BigInt;
+
+    fn nodes() -> Vec<super::Node> {
+        return vec![
+            ((0, 1), binary_split(&Chudnovsky, 0, 1)),
+            ((1, 4), binary_split(&Chudnovsky, 1, 4)),
+        ];
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let nodes = nodes();
+        let references: Vec<&super::Node> = nodes.iter().collect();
+        let bytes = super::encode(20, 2, 4, &references);
+        let checkpoint = super::decode(&bytes).unwrap();
+        assert_eq!(
+            checkpoint,
+            super::Checkpoint {
+                digits: 20,
+                terms: 2,
+                chunks: 4,
+                nodes,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_negative_numbers() {
+        let split = Split {
+            p: BigInt::from(-1),
+            q: BigInt::from(0),
+            b: BigInt::from(1),
+            t: BigInt::from(-256),
+        };
+        let node = ((3, 4), split);
+        let bytes = super::encode(1, 1, 1, &[&node]);
+        assert_eq!(super::decode(&bytes).unwrap().nodes, vec![node]);
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupted_data() {
+        let nodes = nodes();
+        let mut bytes = super::encode(20, 2, 4, &[&nodes[0]]);
+        bytes[30] ^= 1;
+        let error = super::decode(&bytes).unwrap_err();
+        assert_eq!(error.to_string(), "Checkpoint checksum mismatch");
+    }
+
+    #[test]
+    fn test_decode_rejects_other_versions() {
+        let mut bytes = super::encode(20, 2, 4, &[]);
+        bytes[4] = 3;
+        let content_length = bytes.len() - 8;
+        let checksum = super::fnv1a(&bytes[..content_length]);
+        bytes.truncate(content_length);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        let error = super::decode(&bytes).unwrap_err();
+        assert_eq!(error.to_string(), "Unsupported checkpoint version 3");
+    }
+
+    #[test]
+    fn test_decode_rejects_other_files() {
+        let error = super::decode(b"3.14159265358979").unwrap_err();
+        assert_eq!(error.to_string(), "Not a pi checkpoint file");
+    }
+}
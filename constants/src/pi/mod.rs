@@ -0,0 +1,707 @@
+use super::series::{self, Series, Split, Term};
+use super::{Constant, GUARD_DIGITS};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+mod checkpoint;
+
+use checkpoint::Checkpoint;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Each term of the Chudnovsky series adds log10(640320³ / (24 * 6 * 2 * 6)) ≈ 14.18 digits.
+const DIGITS_PER_TERM: f64 = 14.181_647_462_725_477;
+
+/// 640320³ / 24
+const C3_OVER_24: u64 = 10_939_058_860_032_000;
+
+/// Number of series chunks handed to each worker thread, so faster workers can
+/// pick up extra chunks instead of idling.
+const CHUNKS_PER_THREAD: u64 = 4;
+
+/// Seconds between checkpoints saved while the series terms are computed.
+const DEFAULT_CHECKPOINT_INTERVAL: u64 = 60;
+
+/// Range of series terms [a, b).
+type Range = (u64, u64);
+
+/// Binary splitting result together with the range of terms it covers.
+type Node = (Range, Split);
+
+pub struct Pi;
+
+/// Chudnovsky series: 1 / π = 12 Σ (-1)^k (6k)! (13591409 + 545140134k) / ((3k)! (k!)³ 640320^(3k + 3/2))
+/// https://en.wikipedia.org/wiki/Chudnovsky_algorithm
+pub struct Chudnovsky;
+
+impl Series for Chudnovsky {
+    fn term(&self, k: u64) -> Term {
+        let (p, q) = match k {
+            0 => (BigInt::from(1), BigInt::from(1)),
+            _ => (
+                -BigInt::from(6 * k - 5) * (2 * k - 1) * (6 * k - 1),
+                BigInt::from(k).pow(3) * C3_OVER_24,
+            ),
+        };
+        return Term {
+            p,
+            q,
+            a: 13_591_409 + 545_140_134 * BigInt::from(k),
+            b: BigInt::from(1),
+        };
+    }
+
+    fn number_of_terms(&self, digits: u64) -> u64 {
+        return (digits as f64 / DIGITS_PER_TERM) as u64 + 1;
+    }
+}
+
+impl Constant for Pi {
+    fn name(&self) -> &'static str {
+        return "pi";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "π";
+    }
+
+    /// Single threaded `calculate_with` run without progress or checkpoints.
+    fn scaled(&self, digits: u64) -> BigInt {
+        let terms = Chudnovsky.number_of_terms(digits);
+        let job = Job {
+            digits,
+            terms,
+            chunks: number_of_chunks(terms, 1),
+            threads: 1,
+            checkpoint: None,
+            checkpoint_interval: Duration::from_secs(DEFAULT_CHECKPOINT_INTERVAL),
+            reporter: ProgressReporter::new(None, 0),
+        };
+        return chudnovsky(&job, Vec::new())
+            .expect("Calculating without a checkpoint can not fail");
+    }
+}
+
+/// Configuration for `calculate_with`.
+pub struct Options {
+    pub number_of_decimals: u64,
+    /// Worker threads used to compute the series terms.
+    pub threads: usize,
+    /// Receives a `Progress` update every time a unit of work finishes.
+    pub progress: Option<Sender<Progress>>,
+    /// File where the intermediate state is saved. When the file already exists
+    /// the calculation resumes from it.
+    pub checkpoint: Option<PathBuf>,
+    /// Minimum time between checkpoints while the series terms are computed.
+    /// A checkpoint is always saved after every merge level.
+    pub checkpoint_interval: Duration,
+}
+
+impl Options {
+    pub fn new(number_of_decimals: u64) -> Options {
+        return Options {
+            number_of_decimals,
+            threads: 1,
+            progress: None,
+            checkpoint: None,
+            checkpoint_interval: Duration::from_secs(DEFAULT_CHECKPOINT_INTERVAL),
+        };
+    }
+}
+
+/// Units of work completed so far out of the total for the run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub completed: u64,
+    pub total: u64,
+}
+
+struct ProgressReporter {
+    sink: Option<Sender<Progress>>,
+    completed: AtomicU64,
+    total: u64,
+}
+
+impl ProgressReporter {
+    fn new(sink: Option<Sender<Progress>>, total: u64) -> ProgressReporter {
+        return ProgressReporter {
+            sink,
+            completed: AtomicU64::new(0),
+            total,
+        };
+    }
+
+    fn step(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(sink) = &self.sink {
+            // A dropped receiver only means nobody is listening anymore.
+            let _ = sink.send(Progress {
+                completed,
+                total: self.total,
+            });
+        }
+    }
+}
+
+/// Parameters shared by every stage of a single `calculate_with` run.
+struct Job<'a> {
+    digits: u64,
+    terms: u64,
+    chunks: u64,
+    threads: usize,
+    checkpoint: Option<&'a Path>,
+    checkpoint_interval: Duration,
+    reporter: ProgressReporter,
+}
+
+impl<'a> Job<'a> {
+    fn save(&self, nodes: &[&Node]) -> io::Result<()> {
+        match self.checkpoint {
+            Some(path) => checkpoint::save(path, self.digits, self.terms, self.chunks, nodes),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Calculate Pi with the given number of decimal digits.
+pub fn calculate(number_of_decimals: u16) -> String {
+    return Pi.calculate(number_of_decimals as u64);
+}
+
+/// Calculate Pi with the number of decimal digits, threads, progress sink and
+/// checkpoint file in `options`. Fails only when the checkpoint can not be read or saved.
+pub fn calculate_with(options: Options) -> io::Result<String> {
+    let digits = options.number_of_decimals + GUARD_DIGITS;
+    let terms = Chudnovsky.number_of_terms(digits);
+    let resumed = match &options.checkpoint {
+        Some(path) if path.exists() => Some(Checkpoint::load(path)?),
+        _ => None,
+    };
+    let chunks = match &resumed {
+        Some(checkpoint) if checkpoint.digits != digits || checkpoint.terms != terms => {
+            let message = format!(
+                "Checkpoint was created for {} decimals",
+                checkpoint.digits - GUARD_DIGITS
+            );
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+        Some(checkpoint) => checkpoint.chunks,
+        None => number_of_chunks(terms, options.threads),
+    };
+    let job = Job {
+        digits,
+        terms,
+        chunks,
+        threads: options.threads.max(1),
+        checkpoint: options.checkpoint.as_deref(),
+        checkpoint_interval: options.checkpoint_interval,
+        // Leaves, merges, square root, division and conversion to string.
+        reporter: ProgressReporter::new(options.progress, 2 * chunks - 1 + 3),
+    };
+    let resumed_nodes = resumed.map_or(Vec::new(), |checkpoint| checkpoint.nodes);
+    let pi = chudnovsky(&job, resumed_nodes)?;
+    let result = super::convert_to_string(pi, digits, options.number_of_decimals);
+    job.reporter.step();
+    return Ok(result);
+}
+
+fn number_of_chunks(terms: u64, threads: usize) -> u64 {
+    return (threads.max(1) as u64 * CHUNKS_PER_THREAD).min(terms);
+}
+
+/// Splits the terms [0, terms) into contiguous chunks for the worker threads.
+fn split_range(terms: u64, chunks: u64) -> Vec<Range> {
+    return (0..chunks)
+        .map(|i| (terms * i / chunks, terms * (i + 1) / chunks))
+        .collect();
+}
+
+/// π = 426880 √10005 / Σ, with the Chudnovsky series summed by binary splitting.
+/// Returns Pi scaled by 10^digits, truncated to an integer.
+fn chudnovsky(job: &Job, resumed: Vec<Node>) -> io::Result<BigInt> {
+    return thread::scope(|scope| {
+        let sqrt = scope.spawn(|| sqrt_10005(job.digits));
+        let leaves = compute_leaves(job, resumed)?;
+        let (_range, split) = merge_all(job, leaves)?;
+        let sqrt_10005 = sqrt.join().expect("Square root thread panicked");
+        job.reporter.step();
+        let pi = BigInt::from(426880) * sqrt_10005 * split.b * split.q / split.t;
+        job.reporter.step();
+        return Ok(pi);
+    });
+}
+
+/// √10005 scaled by 10^digits.
+fn sqrt_10005(digits: u64) -> BigInt {
+    let one = BigInt::from(10).pow(digits as u32);
+    return (BigInt::from(10005) * &one * &one).sqrt();
+}
+
+/// Computes every chunk of terms that is not already covered by a resumed node,
+/// saving a checkpoint at most once per `checkpoint_interval`.
+fn compute_leaves(job: &Job, resumed: Vec<Node>) -> io::Result<Vec<Node>> {
+    let ranges = split_range(job.terms, job.chunks);
+    let (covered, missing): (Vec<Range>, Vec<Range>) = ranges.into_iter().partition(|(a, b)| {
+        resumed
+            .iter()
+            .any(|((start, end), _)| start <= a && b <= end)
+    });
+    // Every covered chunk was computed and merged into its resumed node.
+    for _ in 0..(2 * covered.len()).saturating_sub(resumed.len()) {
+        job.reporter.step();
+    }
+    let last_saved = Mutex::new(Instant::now());
+    let computed = parallel_map(
+        missing,
+        job.threads,
+        |(a, b)| ((a, b), series::binary_split(&Chudnovsky, a, b)),
+        &job.reporter,
+        |done| {
+            let mut last_saved = last_saved.lock().unwrap();
+            if job.checkpoint.is_none() || last_saved.elapsed() < job.checkpoint_interval {
+                return Ok(());
+            }
+            let nodes: Vec<&Node> = resumed
+                .iter()
+                .chain(done.iter().map(|(_, node)| node))
+                .collect();
+            *last_saved = Instant::now();
+            return job.save(&nodes);
+        },
+    )?;
+    let mut nodes = resumed;
+    nodes.extend(computed);
+    nodes.sort_by_key(|((a, _), _)| *a);
+    job.save(&nodes.iter().collect::<Vec<&Node>>())?;
+    return Ok(nodes);
+}
+
+/// Merges adjacent nodes pairwise, one level at a time, until only one is left.
+fn merge_all(job: &Job, nodes: Vec<Node>) -> io::Result<Node> {
+    let mut nodes = nodes;
+    while nodes.len() > 1 {
+        let carry = match nodes.len() % 2 {
+            1 => nodes.pop(),
+            _ => None,
+        };
+        let mut pairs = Vec::new();
+        let mut iterator = nodes.into_iter();
+        while let (Some(left), Some(right)) = (iterator.next(), iterator.next()) {
+            pairs.push((left, right));
+        }
+        nodes = parallel_map(
+            pairs,
+            job.threads,
+            |(((a, _), left), ((_, b), right))| ((a, b), series::merge(left, right)),
+            &job.reporter,
+            |_| Ok(()),
+        )?;
+        nodes.extend(carry);
+        job.save(&nodes.iter().collect::<Vec<&Node>>())?;
+    }
+    return Ok(nodes.pop().expect("There is always at least one node"));
+}
+
+/// Applies `f` to every item using `threads` workers, preserving the order of the items.
+/// `after_each` is called with the results so far every time an item is done; the
+/// first error it returns stops the workers.
+fn parallel_map<T, R, F, A>(
+    items: Vec<T>,
+    threads: usize,
+    f: F,
+    reporter: &ProgressReporter,
+    after_each: A,
+) -> io::Result<Vec<R>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+    A: Fn(&[(usize, R)]) -> io::Result<()> + Sync,
+{
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::new());
+    let error = Mutex::new(None);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().next();
+                if error.lock().unwrap().is_some() {
+                    return;
+                }
+                match next {
+                    Some((index, item)) => {
+                        let result = f(item);
+                        let mut results = results.lock().unwrap();
+                        results.push((index, result));
+                        reporter.step();
+                        if let Err(after_each_error) = after_each(&results) {
+                            *error.lock().unwrap() = Some(after_each_error);
+                        }
+                    }
+                    None => return,
+                }
+            });
+        }
+    });
+    if let Some(error) = error.into_inner().unwrap() {
+        return Err(error);
+    }
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    return Ok(results.into_iter().map(|(_, result)| result).collect());
+}
+
+/// Unbounded stream of the decimal digits of Pi, starting with the integer part:
+/// 3, 1, 4, 1, 5, 9, ...
+pub fn digits() -> Digits {
+    return Digits {
+        q: BigInt::from(1),
+        r: BigInt::from(0),
+        t: BigInt::from(1),
+        k: 1,
+        n: BigInt::from(3),
+        l: 3,
+    };
+}
+
+/// State of Gibbons' unbounded spigot algorithm. Every produced digit is final,
+/// so consuming more digits continues from where the previous ones stopped.
+/// https://www.cs.ox.ac.uk/jeremy.gibbons/publications/spigot.pdf
+pub struct Digits {
+    q: BigInt,
+    r: BigInt,
+    t: BigInt,
+    k: u64,
+    n: BigInt,
+    l: u64,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        loop {
+            if 4 * &self.q + &self.r - &self.t < &self.n * &self.t {
+                let digit = self
+                    .n
+                    .to_u8()
+                    .expect("Spigot digits are always between 0 and 9");
+                let r = 10 * (&self.r - &self.n * &self.t);
+                self.n = 10 * (3 * &self.q + &self.r) / &self.t - 10 * &self.n;
+                self.q *= 10;
+                self.r = r;
+                return Some(digit);
+            }
+            let r = (2 * &self.q + &self.r) * self.l;
+            let n = (&self.q * (7 * self.k + 2) + &self.r * self.l) / (&self.t * self.l);
+            self.q *= self.k;
+            self.t *= self.l;
+            self.l += 2;
+            self.k += 1;
+            self.n = n;
+            self.r = r;
+        }
+    }
+}
+
+/// Hexadecimal digit of Pi at the given position after the hexadecimal point,
+/// computed without the preceding digits. Position 0 is the first fractional
+/// digit (π = 3.243F6A88...), so `hex_digit_at(0)` returns 0x2.
+pub fn hex_digit_at(position: u64) -> u8 {
+    return (bbp(position) >> 60) as u8;
+}
+
+/// `count` hexadecimal digits of Pi starting at `offset` after the hexadecimal point.
+/// Each BBP evaluation yields several reliable digits, so only one evaluation is
+/// done per chunk of digits.
+pub fn hex_digits(offset: u64, count: u64) -> String {
+    let mut digits = String::new();
+    let mut position = offset;
+    while position < offset + count {
+        let chunk = reliable_hex_digits(position).min(offset + count - position);
+        let fraction = bbp(position);
+        for i in 0..chunk {
+            let digit = (fraction >> (60 - 4 * i)) & 0xF;
+            digits.push_str(&format!("{:X}", digit));
+        }
+        position += chunk;
+    }
+    return digits;
+}
+
+/// Digit extraction form of the Bailey–Borwein–Plouffe formula.
+/// Returns the fractional part of 16^n * π as a 64 bit fixed-point fraction.
+/// https://en.wikipedia.org/wiki/Bailey–Borwein–Plouffe_formula
+fn bbp(n: u64) -> u64 {
+    return (bbp_series(1, n).wrapping_mul(4))
+        .wrapping_sub(bbp_series(4, n).wrapping_mul(2))
+        .wrapping_sub(bbp_series(5, n))
+        .wrapping_sub(bbp_series(6, n));
+}
+
+/// Fractional part of 16^n * Σ 1 / (16^k * (8k + j)) as a 64 bit fixed-point fraction.
+/// Wrapping additions discard the integer part for free.
+fn bbp_series(j: u64, n: u64) -> u64 {
+    let mut sum = 0_u64;
+    for k in 0..=n {
+        let denominator = 8 * k + j;
+        let numerator = modular_pow(16, n - k, denominator);
+        sum = sum.wrapping_add(fixed_point_fraction(numerator as u128, denominator));
+    }
+    let mut shift = 4;
+    let mut k = n + 1;
+    while shift < 64 {
+        let term = fixed_point_fraction(1, 8 * k + j) >> shift;
+        sum = sum.wrapping_add(term);
+        shift += 4;
+        k += 1;
+    }
+    return sum;
+}
+
+/// numerator / denominator scaled by 2^64, for numerator < denominator.
+fn fixed_point_fraction(numerator: u128, denominator: u64) -> u64 {
+    return ((numerator << 64) / denominator as u128) as u64;
+}
+
+/// Every series term is truncated by at most one unit in the last place, so the
+/// accumulated error of `bbp(position)` is below 8 * (position + 16) units.
+fn reliable_hex_digits(position: u64) -> u64 {
+    let error_bits = 64 - (8 * (position + 16)).leading_zeros() as u64;
+    let digits = (64 - error_bits) / 4;
+    return (digits - 1).max(1);
+}
+
+fn modular_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    // Products of two residues only fit in a u64 while the modulus fits in 32 bits.
+    if modulus <= u32::MAX as u64 {
+        return square_and_multiply(base % modulus, exponent, |a, b| a * b % modulus);
+    }
+    let wide_modulus = modulus as u128;
+    return square_and_multiply(base % modulus, exponent, |a, b| {
+        (a as u128 * b as u128 % wide_modulus) as u64
+    });
+}
+
+fn square_and_multiply(base: u64, exponent: u64, multiply: impl Fn(u64, u64) -> u64) -> u64 {
+    let mut result = 1;
+    let mut base = base;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = multiply(result, base);
+        }
+        base = multiply(base, base);
+        exponent /= 2;
+    }
+    return result;
+}
+
+// Unit tests
+
+#[cfg(test)]
+mod tests {
+    use super::super::series::{self, Series};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+    use std::{env, fs, process};
+
+    const PI_10000: &str = include_str!("../../fixtures/pi_10000.txt");
+
+    #[test]
+    fn test_get_pi() {
+        // π = 3.1415926535897932384626433832795028841971
+        assert_eq!(super::calculate(0), "3");
+        assert_eq!(super::calculate(1), "3.1");
+        assert_eq!(super::calculate(2), "3.14");
+        assert_eq!(super::calculate(3), "3.141");
+        assert_eq!(super::calculate(4), "3.1415");
+        assert_eq!(super::calculate(15), "3.141592653589793");
+        assert_eq!(
+            super::calculate(40),
+            "3.1415926535897932384626433832795028841971"
+        );
+        assert_eq!(super::calculate(1000), PI_10000[..1002]);
+        assert_eq!(super::calculate(10000), PI_10000);
+    }
+
+    #[test]
+    fn test_digits() {
+        let digits: String = super::digits().take(1001).map(|d| d.to_string()).collect();
+        assert_eq!(digits, PI_10000.replace(".", "")[..1001]);
+    }
+
+    #[test]
+    fn test_digits_resume_where_they_stopped() {
+        let mut digits = super::digits();
+        let first: Vec<u8> = digits.by_ref().take(5).collect();
+        let next: Vec<u8> = digits.take(5).collect();
+        assert_eq!(first, vec![3, 1, 4, 1, 5]);
+        assert_eq!(next, vec![9, 2, 6, 5, 3]);
+    }
+
+    #[test]
+    fn test_calculate_with_threads() {
+        let mut options = super::Options::new(10000);
+        options.threads = 4;
+        assert_eq!(super::calculate_with(options).unwrap(), PI_10000);
+    }
+
+    #[test]
+    fn test_calculate_with_progress() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut options = super::Options::new(1000);
+        options.threads = 2;
+        options.progress = Some(sender);
+        super::calculate_with(options).unwrap();
+        let updates: Vec<super::Progress> = receiver.iter().collect();
+        // 8 leaves, 7 merges, square root, division and conversion.
+        assert_eq!(updates.len(), 18);
+        assert_eq!(
+            updates.last(),
+            Some(&super::Progress {
+                completed: 18,
+                total: 18
+            })
+        );
+    }
+
+    #[test]
+    fn test_number_of_chunks() {
+        assert_eq!(super::number_of_chunks(10, 1), 4);
+        assert_eq!(super::number_of_chunks(2, 4), 2);
+    }
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(
+            super::split_range(10, 4),
+            vec![(0, 2), (2, 5), (5, 7), (7, 10)]
+        );
+        assert_eq!(super::split_range(2, 2), vec![(0, 1), (1, 2)]);
+    }
+
+    fn checkpoint_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("pi_{}_{}.checkpoint", name, process::id()));
+        let _ = fs::remove_file(&path);
+        return path;
+    }
+
+    /// Saves the checkpoint a 1000 decimals run would have left behind if it was
+    /// interrupted after computing the given spans of chunks (first and last chunk).
+    fn save_interrupted_run(path: &Path, chunk_spans: &[(usize, usize)]) {
+        let digits = 1000 + super::GUARD_DIGITS;
+        let terms = super::Chudnovsky.number_of_terms(digits);
+        let chunks = super::number_of_chunks(terms, 1);
+        let ranges = super::split_range(terms, chunks);
+        let nodes: Vec<super::Node> = chunk_spans
+            .iter()
+            .map(|&(first, last)| {
+                let range = (ranges[first].0, ranges[last].1);
+                return (
+                    range,
+                    series::binary_split(&super::Chudnovsky, range.0, range.1),
+                );
+            })
+            .collect();
+        let references: Vec<&super::Node> = nodes.iter().collect();
+        super::checkpoint::save(path, digits, terms, chunks, &references).unwrap();
+    }
+
+    fn resume(path: &Path) -> String {
+        let mut options = super::Options::new(1000);
+        options.checkpoint = Some(path.to_path_buf());
+        return super::calculate_with(options).unwrap();
+    }
+
+    #[test]
+    fn test_resume_after_some_chunks() {
+        let path = checkpoint_path("chunks");
+        save_interrupted_run(&path, &[(0, 0), (2, 2)]);
+        assert_eq!(resume(&path), PI_10000[..1002]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_resume_after_some_merges() {
+        let path = checkpoint_path("merges");
+        save_interrupted_run(&path, &[(0, 1), (3, 3)]);
+        assert_eq!(resume(&path), PI_10000[..1002]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_checkpointed_run_resumes_identically() {
+        let path = checkpoint_path("complete");
+        let mut options = super::Options::new(1000);
+        options.checkpoint = Some(path.clone());
+        options.checkpoint_interval = Duration::from_secs(0);
+        let first_run = super::calculate_with(options).unwrap();
+        let checkpoint = super::Checkpoint::load(&path).unwrap();
+        assert_eq!(checkpoint.nodes.len(), 1);
+        assert_eq!(checkpoint.nodes[0].0, (0, checkpoint.terms));
+        assert_eq!(resume(&path), first_run);
+        assert_eq!(first_run, PI_10000[..1002]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_resume_rejects_checkpoint_of_other_run() {
+        let path = checkpoint_path("other");
+        save_interrupted_run(&path, &[(0, 0)]);
+        let mut options = super::Options::new(500);
+        options.checkpoint = Some(path.clone());
+        let error = super::calculate_with(options).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Checkpoint was created for 1000 decimals"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_chudnovsky_term() {
+        let term = super::Chudnovsky.term(1);
+        assert_eq!(term.p, (-5).into());
+        assert_eq!(term.q, super::C3_OVER_24.into());
+        assert_eq!(term.a, (13_591_409 + 545_140_134_i64).into());
+    }
+
+    #[test]
+    fn test_modular_pow() {
+        assert_eq!(super::modular_pow(16, 0, 7), 1);
+        assert_eq!(super::modular_pow(16, 3, 1), 0);
+        assert_eq!(super::modular_pow(2, 10, 1000), 24);
+        assert_eq!(
+            super::modular_pow(16, 1_000_000_000, 8_000_000_001),
+            5_115_004_099
+        );
+    }
+
+    #[test]
+    fn test_reliable_hex_digits() {
+        assert_eq!(super::reliable_hex_digits(0), 13);
+        assert_eq!(super::reliable_hex_digits(1_000_000_000), 6);
+    }
+
+    #[test]
+    fn test_hex_digit_at() {
+        // π = 3.243F6A8885A308D313198A2E03707344A4093822299F31D008
+        assert_eq!(super::hex_digit_at(0), 0x2);
+        assert_eq!(super::hex_digit_at(3), 0xF);
+        assert_eq!(
+            super::hex_digits(0, 50),
+            "243F6A8885A308D313198A2E03707344A4093822299F31D008"
+        );
+        assert_eq!(super::hex_digits(100_000, 8), "35EA16C4");
+        // Digits starting at the millionth position: 26C65E52CB4593
+        assert_eq!(super::hex_digit_at(999_999), 0x2);
+    }
+}
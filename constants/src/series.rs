@@ -0,0 +1,135 @@
+use num_bigint::BigInt;
+
+/// Term k of a series S = Σ a(k) / b(k) * p(0) * ... * p(k) / (q(0) * ... * q(k)).
+pub struct Term {
+    pub p: BigInt,
+    pub q: BigInt,
+    pub a: BigInt,
+    pub b: BigInt,
+}
+
+/// Series definition of a constant, summed exactly with binary splitting.
+/// https://en.wikipedia.org/wiki/Binary_splitting
+pub trait Series {
+    fn term(&self, k: u64) -> Term;
+
+    /// Terms needed for the truncation error of the series to stay below 10^-digits.
+    fn number_of_terms(&self, digits: u64) -> u64;
+
+    /// Exact sum of the first `number_of_terms(digits)` terms.
+    fn sum(&self, digits: u64) -> Sum {
+        let split = binary_split(self, 0, self.number_of_terms(digits));
+        return Sum {
+            numerator: split.t,
+            denominator: split.b * split.q,
+        };
+    }
+}
+
+/// Partial sum of a series as an exact fraction.
+pub struct Sum {
+    pub numerator: BigInt,
+    pub denominator: BigInt,
+}
+
+impl Sum {
+    /// The sum scaled by 10^digits, truncated to an integer.
+    pub fn scaled(&self, digits: u64) -> BigInt {
+        return &self.numerator * BigInt::from(10).pow(digits as u32) / &self.denominator;
+    }
+}
+
+/// P, Q, B and T of the terms in the range [a, b).
+#[derive(Debug, PartialEq)]
+pub struct Split {
+    pub p: BigInt,
+    pub q: BigInt,
+    pub b: BigInt,
+    pub t: BigInt,
+}
+
+pub fn binary_split<S: Series + ?Sized>(series: &S, a: u64, b: u64) -> Split {
+    match b - a {
+        1 => {
+            let term = series.term(a);
+            return Split {
+                t: term.a * &term.p,
+                p: term.p,
+                q: term.q,
+                b: term.b,
+            };
+        }
+        _ => {
+            let m = (a + b) / 2;
+            return merge(binary_split(series, a, m), binary_split(series, m, b));
+        }
+    }
+}
+
+/// Combines the splits of [a, m) and [m, b) into the split of [a, b).
+pub fn merge(left: Split, right: Split) -> Split {
+    return Split {
+        t: &right.b * &right.q * left.t + &left.b * &left.p * right.t,
+        p: left.p * right.p,
+        q: left.q * right.q,
+        b: left.b * right.b,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    /// Σ 1 / 2^k for k in 0..n
+    struct Geometric;
+
+    impl super::Series for Geometric {
+        fn term(&self, k: u64) -> super::Term {
+            return super::Term {
+                p: BigInt::from(1),
+                q: BigInt::from(if k == 0 { 1 } else { 2 }),
+                a: BigInt::from(1),
+                b: BigInt::from(1),
+            };
+        }
+
+        fn number_of_terms(&self, digits: u64) -> u64 {
+            return digits;
+        }
+    }
+
+    /// Σ 1 / (k + 1) for k in 0..n
+    struct Harmonic;
+
+    impl super::Series for Harmonic {
+        fn term(&self, k: u64) -> super::Term {
+            return super::Term {
+                p: BigInt::from(1),
+                q: BigInt::from(1),
+                a: BigInt::from(1),
+                b: BigInt::from(k + 1),
+            };
+        }
+
+        fn number_of_terms(&self, digits: u64) -> u64 {
+            return digits;
+        }
+    }
+
+    #[test]
+    fn test_sum() {
+        use super::Series;
+        let sum = Geometric.sum(4);
+        // 1 + 1/2 + 1/4 + 1/8 = 15/8
+        assert_eq!(sum.numerator * 8, sum.denominator * 15);
+        let sum = Harmonic.sum(3);
+        // 1 + 1/2 + 1/3 = 11/6
+        assert_eq!(sum.numerator * 6, sum.denominator * 11);
+    }
+
+    #[test]
+    fn test_scaled() {
+        use super::Series;
+        assert_eq!(Harmonic.sum(3).scaled(4), BigInt::from(18333));
+    }
+}
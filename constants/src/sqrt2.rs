@@ -0,0 +1,33 @@
+use super::Constant;
+use num_bigint::BigInt;
+
+pub struct Sqrt2;
+
+impl Constant for Sqrt2 {
+    fn name(&self) -> &'static str {
+        return "sqrt2";
+    }
+
+    fn symbol(&self) -> &'static str {
+        return "√2";
+    }
+
+    /// Integer square root of 2 * 10^(2 * digits), which is exact for every digit.
+    fn scaled(&self, digits: u64) -> BigInt {
+        return (BigInt::from(2) * BigInt::from(10).pow(2 * digits as u32)).sqrt();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Constant;
+
+    const SQRT2_1000: &str = include_str!("../fixtures/sqrt2_1000.txt");
+
+    #[test]
+    fn test_calculate() {
+        assert_eq!(super::Sqrt2.calculate(0), "1");
+        assert_eq!(super::Sqrt2.calculate(5), "1.41421");
+        assert_eq!(super::Sqrt2.calculate(1000), SQRT2_1000);
+    }
+}
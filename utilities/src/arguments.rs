@@ -0,0 +1,47 @@
+use std::str::FromStr;
+
+/// Value following `flag` in the command line arguments, e.g. `--digits 100`.
+pub fn flag_value<T: FromStr>(args: &[String], flag: &str) -> Option<T> {
+    return flag_string(args, flag).and_then(|value| value.parse().ok());
+}
+
+pub fn flag_string<'a>(args: &'a [String], flag: &str) -> Option<&'a String> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    return args.get(index + 1);
+}
+
+pub fn has_flag(args: &[String], flag: &str) -> bool {
+    return args.iter().any(|arg| arg == flag);
+}
+
+#[cfg(test)]
+mod tests {
+    fn args() -> Vec<String> {
+        return vec!["--digits", "100", "--stream", "--output", "pi.txt"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+    }
+
+    #[test]
+    fn test_flag_value() {
+        assert_eq!(super::flag_value::<u64>(&args(), "--digits"), Some(100));
+        assert_eq!(super::flag_value::<u64>(&args(), "--output"), None);
+        assert_eq!(super::flag_value::<u64>(&args(), "--count"), None);
+    }
+
+    #[test]
+    fn test_flag_string() {
+        assert_eq!(
+            super::flag_string(&args(), "--output").map(String::as_str),
+            Some("pi.txt")
+        );
+        assert_eq!(super::flag_string(&args(), "--radix"), None);
+    }
+
+    #[test]
+    fn test_has_flag() {
+        assert!(super::has_flag(&args(), "--stream"));
+        assert!(!super::has_flag(&args(), "--hex"));
+    }
+}
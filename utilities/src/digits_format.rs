@@ -0,0 +1,256 @@
+extern crate num_bigint;
+
+use super::arguments;
+use num_bigint::BigUint;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Extra decimal digits computed beyond the ones needed, so the truncation errors
+/// of series, divisions and radix conversions don't reach the written digits.
+pub const GUARD_DIGITS: u64 = 10;
+
+/// How the digits of a constant like Pi or e are written out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigitFormat {
+    /// Base of the written digits, from 2 to 36.
+    pub radix: u32,
+    /// Number of digits in each space separated block, 0 for no blocks.
+    pub group_size: usize,
+    /// Number of fractional digits in each line, followed by the offset of the
+    /// last digit in the line like published digit tables. 0 for a single line.
+    pub digits_per_line: usize,
+    /// File the digits are written to instead of stdout.
+    pub output: Option<PathBuf>,
+}
+
+impl DigitFormat {
+    pub fn new() -> DigitFormat {
+        return DigitFormat {
+            radix: 10,
+            group_size: 0,
+            digits_per_line: 0,
+            output: None,
+        };
+    }
+
+    /// Reads `--radix <2-36>`, `--group <n>`, `--line <n>` and `--output <file>`.
+    pub fn from_args(args: &[String]) -> Result<DigitFormat, String> {
+        let radix = arguments::flag_value(args, "--radix").unwrap_or(10);
+        if !(2..=36).contains(&radix) {
+            return Err(format!(
+                "The radix needs to be between 2 and 36, got {}",
+                radix
+            ));
+        }
+        return Ok(DigitFormat {
+            radix,
+            group_size: arguments::flag_value(args, "--group").unwrap_or(0),
+            digits_per_line: arguments::flag_value(args, "--line").unwrap_or(0),
+            output: arguments::flag_string(args, "--output").map(PathBuf::from),
+        });
+    }
+
+    /// Decimal digits that need to be calculated to write `number_of_digits`
+    /// correct fractional digits in this radix.
+    pub fn decimals_needed(&self, number_of_digits: u64) -> u64 {
+        match self.radix {
+            10 => number_of_digits,
+            radix => {
+                (number_of_digits as f64 * (radix as f64).log10()).ceil() as u64 + GUARD_DIGITS
+            }
+        }
+    }
+
+    /// Formats a decimal number like "3.14159..." with `number_of_digits`
+    /// fractional digits in this radix, grouped and split in lines.
+    pub fn format(&self, decimal: &str, number_of_digits: u64) -> String {
+        let (integer, fraction) = to_radix(decimal, self.radix, number_of_digits);
+        if fraction.is_empty() {
+            return integer;
+        }
+        if self.digits_per_line == 0 {
+            return format!("{}.{}", integer, self.group(&fraction));
+        }
+        let full_line_width = self.group(&"0".repeat(self.digits_per_line)).len();
+        let mut lines = vec![format!("{}.", integer)];
+        let mut offset = 0;
+        for line in fraction.as_bytes().chunks(self.digits_per_line) {
+            offset += line.len();
+            let line = self.group(std::str::from_utf8(line).unwrap());
+            lines.push(format!(
+                "{:<width$}  : {}",
+                line,
+                offset,
+                width = full_line_width
+            ));
+        }
+        return lines.join("\n");
+    }
+
+    /// Writes the formatted digits to the output file, or prints them with the title.
+    pub fn write(&self, title: &str, formatted: &str) -> io::Result<()> {
+        match &self.output {
+            Some(path) => fs::write(path, format!("{}\n", formatted)),
+            None => {
+                println!("{}{}", title, formatted);
+                return Ok(());
+            }
+        }
+    }
+
+    fn group(&self, digits: &str) -> String {
+        if self.group_size == 0 {
+            return digits.to_string();
+        }
+        let blocks: Vec<&str> = digits
+            .as_bytes()
+            .chunks(self.group_size)
+            .map(|block| std::str::from_utf8(block).unwrap())
+            .collect();
+        return blocks.join(" ");
+    }
+}
+
+impl Default for DigitFormat {
+    fn default() -> DigitFormat {
+        return DigitFormat::new();
+    }
+}
+
+/// Converts a decimal number like "3.14159..." into its integer part and
+/// `number_of_digits` truncated fractional digits in the given radix.
+pub fn to_radix(decimal: &str, radix: u32, number_of_digits: u64) -> (String, String) {
+    let (integer, fraction) = match decimal.find('.') {
+        Some(index) => (&decimal[..index], &decimal[index + 1..]),
+        None => (decimal, ""),
+    };
+    if radix == 10 {
+        let number_of_digits = (number_of_digits as usize).min(fraction.len());
+        return (
+            integer.to_string(),
+            fraction[..number_of_digits].to_string(),
+        );
+    }
+    let integer = parse_decimal(integer).to_str_radix(radix).to_uppercase();
+    if number_of_digits == 0 || fraction.is_empty() {
+        return (integer, String::new());
+    }
+    let scaled = parse_decimal(fraction) * BigUint::from(radix).pow(number_of_digits as u32)
+        / BigUint::from(10_u32).pow(fraction.len() as u32);
+    let digits = scaled.to_str_radix(radix).to_uppercase();
+    let padding = "0".repeat(number_of_digits as usize - digits.len());
+    return (integer, padding + &digits);
+}
+
+fn parse_decimal(digits: &str) -> BigUint {
+    return BigUint::parse_bytes(digits.as_bytes(), 10).unwrap_or_default();
+}
+
+#[cfg(test)]
+mod tests {
+    const PI_50: &str = "3.14159265358979323846264338327950288419716939937510";
+
+    fn format(radix: u32, group_size: usize, digits_per_line: usize) -> super::DigitFormat {
+        return super::DigitFormat {
+            radix,
+            group_size,
+            digits_per_line,
+            output: None,
+        };
+    }
+
+    #[test]
+    fn test_to_radix() {
+        assert_eq!(
+            super::to_radix(PI_50, 10, 5),
+            ("3".to_string(), "14159".to_string())
+        );
+        assert_eq!(
+            super::to_radix(PI_50, 16, 16),
+            ("3".to_string(), "243F6A8885A308D3".to_string())
+        );
+        assert_eq!(
+            super::to_radix(PI_50, 2, 8),
+            ("11".to_string(), "00100100".to_string())
+        );
+        assert_eq!(
+            super::to_radix("2.71828182845904523536", 36, 4),
+            ("2".to_string(), "PUW5".to_string())
+        );
+        assert_eq!(
+            super::to_radix("255", 16, 3),
+            ("FF".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_decimals_needed() {
+        assert_eq!(format(10, 0, 0).decimals_needed(100), 100);
+        assert_eq!(
+            format(2, 0, 0).decimals_needed(100),
+            31 + super::GUARD_DIGITS
+        );
+        assert_eq!(
+            format(16, 0, 0).decimals_needed(100),
+            121 + super::GUARD_DIGITS
+        );
+    }
+
+    #[test]
+    fn test_format_plain() {
+        assert_eq!(format(10, 0, 0).format(PI_50, 10), "3.1415926535");
+        assert_eq!(format(16, 0, 0).format(PI_50, 8), "3.243F6A88");
+        assert_eq!(format(10, 0, 0).format("3", 0), "3");
+    }
+
+    #[test]
+    fn test_format_groups() {
+        assert_eq!(format(10, 5, 0).format(PI_50, 12), "3.14159 26535 89");
+    }
+
+    #[test]
+    fn test_format_lines() {
+        let expectation = "3.\n\
+                           14159 26535  : 10\n\
+                           89793 23846  : 20\n\
+                           264          : 23";
+        assert_eq!(format(10, 5, 10).format(PI_50, 23), expectation);
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let path = std::env::temp_dir().join(format!("digits_{}.txt", std::process::id()));
+        let mut format = format(10, 0, 0);
+        format.output = Some(path.clone());
+        format.write("π: ", "3.14159").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3.14159\n");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_from_args() {
+        let args: Vec<String> = vec![
+            "--radix", "16", "--group", "10", "--line", "50", "--output", "pi.txt",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let format = super::DigitFormat::from_args(&args).unwrap();
+        assert_eq!(format.radix, 16);
+        assert_eq!(format.group_size, 10);
+        assert_eq!(format.digits_per_line, 50);
+        assert_eq!(format.output, Some(std::path::PathBuf::from("pi.txt")));
+        assert_eq!(
+            super::DigitFormat::from_args(&[]),
+            Ok(super::DigitFormat::new())
+        );
+    }
+
+    #[test]
+    fn test_from_args_rejects_invalid_radix() {
+        let args = vec!["--radix".to_string(), "37".to_string()];
+        let error = super::DigitFormat::from_args(&args).unwrap_err();
+        assert_eq!(error, "The radix needs to be between 2 and 36, got 37");
+    }
+}
@@ -1,4 +1,9 @@
+#![allow(clippy::needless_return)]
+
 pub mod read_number_input;
 pub mod geometry;
 pub mod graphics;
-pub mod block;
\ No newline at end of file
+#[allow(clippy::clone_on_copy)]
+pub mod block;
+pub mod arguments;
+pub mod digits_format;